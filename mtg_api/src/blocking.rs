@@ -0,0 +1,151 @@
+//! Synchronous versions of the requests, for use without an async runtime
+//!
+//! Enabled by the `blocking` feature. These must not be called from within an async
+//! runtime, as [`reqwest::blocking`] starts one of its own.
+#![deny(missing_docs)]
+use std::sync::OnceLock;
+
+use reqwest::blocking::Response;
+
+use crate::{
+    check_count, check_status, encode_segment, exact_name, page_url, APIError, BASE_URL,
+    MAX_PAGE_SIZE,
+};
+
+/// A blocking client for the MTG API, mirroring [`crate::MtgClient`]
+///
+/// The free functions of this module go through a single shared client, created on first use.
+#[derive(Clone, Debug)]
+pub struct Client {
+    http: reqwest::blocking::Client,
+    base_url: String,
+}
+
+impl Default for Client {
+    fn default() -> Self {
+        Client {
+            http: reqwest::blocking::Client::new(),
+            base_url: BASE_URL.to_owned(),
+        }
+    }
+}
+
+impl Client {
+    /// Create a client with its own connection pool
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send requests to another server, such as a mirror or a mock, instead of [`BASE_URL`]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_owned();
+        self
+    }
+
+    /// URL of the cards endpoint on the configured server
+    fn cards_url(&self) -> String {
+        format!("{}/cards", self.base_url)
+    }
+
+    /// Perform a GET request, erroring unless the status code is a success
+    fn get_request(&self, url: &str) -> Result<Response, APIError> {
+        // Perform the GET request
+        let response = self.http.get(url).send()?;
+
+        // Check if the request was successful
+        check_status(response.status(), response.headers())?;
+        Ok(response)
+    }
+
+    /// Find a card by its numerical ID
+    pub fn card_id_info(&self, card_id: &str) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!("{}/{}", self.cards_url(), encode_segment(card_id));
+
+        // Perform the GET request
+        self.get_request(&url)
+    }
+
+    /// Find a card by its exact name
+    ///
+    /// Errors with [`APIError::NoSuchCardName`] if no card has that name.
+    pub fn card_exact_name_info(&self, card_name: &str) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!("{}?name={}", self.cards_url(), exact_name(card_name));
+
+        // Perform the GET request
+        let response = self.get_request(&url)?;
+        check_count(response.headers(), card_name)?;
+        Ok(response)
+    }
+
+    /// Get a page of [`MAX_PAGE_SIZE`] cards
+    pub fn card_page(&self, page_number: &str) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = page_url(&self.cards_url(), page_number, MAX_PAGE_SIZE);
+
+        // Perform the GET request
+        self.get_request(&url)
+    }
+}
+
+/// The client behind the blocking functions, created on first use
+fn shared() -> &'static Client {
+    static SHARED: OnceLock<Client> = OnceLock::new();
+    SHARED.get_or_init(Client::new)
+}
+
+/// Find a card by its numerical ID
+pub fn card_id_info(card_id: &str) -> Result<Response, APIError> {
+    shared().card_id_info(card_id)
+}
+
+/// Find a card by its exact name
+///
+/// Errors with [`APIError::NoSuchCardName`] if no card has that name.
+pub fn card_exact_name_info(card_name: &str) -> Result<Response, APIError> {
+    shared().card_exact_name_info(card_name)
+}
+
+/// Get a page of [`MAX_PAGE_SIZE`] cards
+pub fn card_page(page_number: &str) -> Result<Response, APIError> {
+    shared().card_page(page_number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock::{json, respond, serve};
+
+    #[test]
+    fn fetch_id_blocking() {
+        let narset = card_id_info("386616").unwrap();
+        let body: serde_json::Value = serde_json::from_str(&narset.text().unwrap()).unwrap();
+        assert_eq!(body["card"]["name"], "Narset, Enlightened Master");
+
+        match card_id_info("173132123") {
+            Err(e @ APIError::NotFound {}) => {
+                assert_eq!(e.status_code(), Some(reqwest::StatusCode::NOT_FOUND))
+            }
+            other => panic!("Expected NotFound, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn blocking_base_url() {
+        // The mock server runs on the worker threads of its own runtime
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let card = json("{\"card\":{\"name\":\"Mock\"}}");
+        let missing = respond("404 Not Found", &[], "");
+        let server = runtime.block_on(serve(vec![card, missing]));
+
+        let client = Client::new().with_base_url(&format!("{}/", server.url));
+        let body = client.card_id_info("386616").unwrap().text().unwrap();
+        assert_eq!(body, "{\"card\":{\"name\":\"Mock\"}}");
+        match client.card_id_info("173132123") {
+            Err(APIError::NotFound {}) => (),
+            other => panic!("Expected NotFound, got {:?}", other),
+        }
+        assert_eq!(server.paths(), vec!["/cards/386616", "/cards/173132123"]);
+    }
+}
@@ -0,0 +1,880 @@
+#![deny(missing_docs)]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock, RwLock};
+use std::time::Duration;
+
+use reqwest::{Response, StatusCode};
+
+use crate::{
+    check_for_empty, check_status, encode, encode_segment, exact_name, page_url, require_cards,
+    retry_after, APIError, CardQuery, BASE_URL, MAX_PAGE_SIZE,
+};
+
+/// A client for the MTG API which reuses one connection pool for all of its requests
+///
+/// The free functions of this crate go through a single shared client, created on first use.
+#[derive(Clone, Debug)]
+pub struct MtgClient {
+    http: reqwest::Client,
+    timeout: Option<Duration>,
+    follow_redirects: bool,
+    base_url: String,
+    max_retries: u32,
+    budget: Option<Arc<RequestBudget>>,
+    sets_cache: Arc<RwLock<Option<String>>>,
+}
+
+/// A number of requests shared by a client and all of its clones
+#[derive(Debug)]
+struct RequestBudget {
+    limit: usize,
+    remaining: AtomicUsize,
+}
+
+impl RequestBudget {
+    /// Take one request from the budget, erroring if none are left
+    fn spend(&self) -> Result<(), APIError> {
+        self.remaining
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+            .map(|_| ())
+            .map_err(|_| APIError::BudgetExhausted {})
+    }
+}
+
+impl Default for MtgClient {
+    fn default() -> Self {
+        MtgClient {
+            http: reqwest::Client::new(),
+            timeout: None,
+            follow_redirects: true,
+            base_url: BASE_URL.to_owned(),
+            max_retries: 0,
+            budget: None,
+            sets_cache: Arc::default(),
+        }
+    }
+}
+
+impl MtgClient {
+    /// Create a client with its own connection pool
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send requests to another server, such as a mirror or a mock, instead of [`BASE_URL`]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_owned();
+        self
+    }
+
+    /// URL of the cards endpoint on the configured server
+    pub(crate) fn cards_url(&self) -> String {
+        format!("{}/cards", self.base_url)
+    }
+
+    /// URL of the sets endpoint on the configured server
+    fn sets_url(&self) -> String {
+        format!("{}/sets", self.base_url)
+    }
+
+    /// Give up on any request which takes longer than `timeout`, by default requests never time out
+    ///
+    /// A request which times out errors with [`APIError::Timeout`].
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self.rebuild_http()
+    }
+
+    /// Whether to follow redirects, which happens by default
+    ///
+    /// When not followed a redirect errors with [`APIError::Redirected`], holding where the
+    /// endpoint has moved to.
+    pub fn follow_redirects(mut self, follow: bool) -> Self {
+        self.follow_redirects = follow;
+        self.rebuild_http()
+    }
+
+    /// Build a new connection pool with the timeout and redirect policy of the client
+    fn rebuild_http(mut self) -> Self {
+        let redirects = match self.follow_redirects {
+            true => reqwest::redirect::Policy::default(),
+            false => reqwest::redirect::Policy::none(),
+        };
+        let mut builder = reqwest::Client::builder().redirect(redirects);
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        self.http = builder.build().expect("TLS backend cannot be initialized");
+        self
+    }
+
+    /// Retry rate limited requests up to `n` times, by default they are not retried
+    ///
+    /// Before each retry the client sleeps for the `Retry-After` the API asked for, or
+    /// backs off exponentially from one second if it gave none.
+    pub fn with_max_retries(mut self, n: u32) -> Self {
+        self.max_retries = n;
+        self
+    }
+
+    /// Allow at most `n` requests, retries included, by default there is no limit
+    ///
+    /// The budget is shared with every clone of the client, so it bounds a whole batch of
+    /// concurrent fetches. Once it is spent requests error with
+    /// [`APIError::BudgetExhausted`] without being sent.
+    pub fn with_request_budget(mut self, n: usize) -> Self {
+        self.budget = Some(Arc::new(RequestBudget {
+            limit: n,
+            remaining: AtomicUsize::new(n),
+        }));
+        self
+    }
+
+    /// Requests left in the budget, `None` if the client has no budget
+    pub fn remaining_budget(&self) -> Option<usize> {
+        self.budget
+            .as_ref()
+            .map(|b| b.remaining.load(Ordering::SeqCst))
+    }
+
+    /// Refill the budget to the size it was given, for this client and its clones
+    pub fn reset_budget(&self) {
+        if let Some(budget) = &self.budget {
+            budget.remaining.store(budget.limit, Ordering::SeqCst);
+        }
+    }
+
+    /// Start a [`CardQuery`] which is sent through this client
+    pub fn query(&self) -> CardQuery<'_> {
+        CardQuery::with_client(self)
+    }
+
+    /// Perform a GET request, erroring unless the status code is a success
+    pub(crate) async fn get_request(&self, url: &str) -> Result<Response, APIError> {
+        let mut attempt = 0;
+        loop {
+            // Count the request against the budget, if any
+            if let Some(budget) = &self.budget {
+                budget.spend()?;
+            }
+
+            // Perform the GET request
+            let response = self.http.get(url).send().await?;
+
+            // Wait and try again if rate limited
+            if response.status() == StatusCode::TOO_MANY_REQUESTS && attempt < self.max_retries {
+                tokio::time::sleep(retry_delay(&response, attempt)).await;
+                attempt += 1;
+                continue;
+            }
+
+            // Check if the request was successful
+            check_status(response.status(), response.headers())?;
+            return Ok(response);
+        }
+    }
+
+    /// Find a card by its numerical ID
+    pub async fn card_id_info(&self, card_id: &str) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!("{}/{}", self.cards_url(), encode_segment(card_id));
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Find a card by its exact name
+    ///
+    /// Errors with [`APIError::NoSuchCardName`] if no card has that name.
+    pub async fn card_exact_name_info(&self, card_name: &str) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!("{}?name={}", self.cards_url(), exact_name(card_name));
+
+        // Perform the GET request
+        require_cards(self.get_request(&url).await?, card_name)
+    }
+
+    /// Find cards by their Gatherer multiverse id
+    pub async fn card_by_multiverseid(&self, id: u64) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!("{}?multiverseid={}", self.cards_url(), id);
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Find every card whose name contains the fragment
+    ///
+    /// Errors with [`APIError::NoSuchCardName`] if no card matches.
+    pub async fn card_name_contains(&self, fragment: &str) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!("{}?name={}", self.cards_url(), encode(fragment));
+
+        // Perform the GET request
+        require_cards(self.get_request(&url).await?, fragment)
+    }
+
+    /// Find cards by their name in another language, e.g. `("Blitzschlag", "German")`
+    ///
+    /// Errors with [`APIError::NoSuchCardName`] if no card has that name in the language.
+    pub async fn card_by_name_in_language(
+        &self,
+        name: &str,
+        language: &str,
+    ) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!(
+            "{}?name={}&language={}",
+            self.cards_url(),
+            encode(name),
+            encode(language)
+        );
+
+        // Perform the GET request
+        require_cards(self.get_request(&url).await?, name)
+    }
+
+    /// Find cards which are all of the given colors, e.g. `["red", "white"]`
+    ///
+    /// Colors are case-insensitive and an empty slice returns all cards.
+    pub async fn card_by_colors(&self, colors: &[&str]) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = match colors.is_empty() {
+            true => self.cards_url(),
+            false => format!(
+                "{}?colors={}",
+                self.cards_url(),
+                encode(&colors.join(",").to_lowercase())
+            ),
+        };
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Find cards which have all of the given types, e.g. `["Artifact", "Creature"]`
+    pub async fn card_by_types(&self, types: &[&str]) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!("{}?types={}", self.cards_url(), encode(&types.join(",")));
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Find cards which have all of the given subtypes, e.g. `["Goblin", "Warrior"]`
+    pub async fn card_by_subtypes(&self, subtypes: &[&str]) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!(
+            "{}?subtypes={}",
+            self.cards_url(),
+            encode(&subtypes.join(","))
+        );
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Find cards which have all of the given supertypes, e.g. `["Legendary"]`
+    pub async fn card_by_supertypes(&self, supertypes: &[&str]) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!(
+            "{}?supertypes={}",
+            self.cards_url(),
+            encode(&supertypes.join(","))
+        );
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Find cards with a converted mana cost of at least `min` or at most `max`
+    ///
+    /// The API reads one `cmc` value per query. Equal bounds find cards of exactly that cost,
+    /// other pairs send only `max`, so cards below `min` are left for the caller to filter out.
+    /// Leaving out both returns every card.
+    pub async fn card_by_cmc_range(
+        &self,
+        min: Option<f32>,
+        max: Option<f32>,
+    ) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = match (min, max) {
+            (Some(min), Some(max)) if min == max => format!("{}?cmc={}", self.cards_url(), min),
+            (Some(min), None) => format!("{}?cmc=gte{}", self.cards_url(), min),
+            (_, Some(max)) => format!("{}?cmc=lte{}", self.cards_url(), max),
+            (None, None) => self.cards_url(),
+        };
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Search cards with query parameters not otherwise wrapped, e.g. `[("gameFormat", "Modern")]`
+    ///
+    /// Keys and values are URL-encoded, no parameters returns every card.
+    pub async fn card_search_raw(&self, params: &[(&str, &str)]) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let query: Vec<String> = params
+            .iter()
+            .map(|(key, value)| format!("{}={}", encode(key), encode(value)))
+            .collect();
+        let url = match query.is_empty() {
+            true => self.cards_url(),
+            false => format!("{}?{}", self.cards_url(), query.join("&")),
+        };
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Find cards of the given rarity, e.g. `"Mythic"` or `"Basic Land"`
+    pub async fn card_by_rarity(&self, rarity: &str) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!("{}?rarity={}", self.cards_url(), encode(rarity));
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Find cards printed in the set with the given code, e.g. `"KTK"`
+    ///
+    /// The code is case-insensitive.
+    pub async fn card_by_set(&self, set_code: &str) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!(
+            "{}?set={}",
+            self.cards_url(),
+            encode(&set_code.trim().to_uppercase())
+        );
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Find cards printed in the set with the given name, e.g. `"Khans of Tarkir"`
+    pub async fn card_by_set_name(&self, name: &str) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!("{}?setName={}", self.cards_url(), encode(name));
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Find cards whose rules text contains the fragment, e.g. `"draw a card"`
+    pub async fn card_by_text(&self, fragment: &str) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!("{}?text={}", self.cards_url(), encode(fragment));
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Find cards whose flavor text contains the fragment
+    pub async fn card_by_flavor(&self, fragment: &str) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!("{}?flavor={}", self.cards_url(), encode(fragment));
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Find cards illustrated by the given artist, e.g. `"Rebecca Guay"`
+    pub async fn card_by_artist(&self, artist: &str) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!("{}?artist={}", self.cards_url(), encode(artist));
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Find cards with the given legality in a format, e.g. `("Commander", "Legal")`
+    pub async fn card_by_legality(
+        &self,
+        format: &str,
+        legality: &str,
+    ) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!(
+            "{}?gameFormat={}&legality={}",
+            self.cards_url(),
+            encode(format),
+            encode(legality)
+        );
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Get the metadata of every set
+    pub async fn sets_all(&self) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = self.sets_url();
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Fetch the metadata of every set and keep it for [`MtgClient::sets_body`]
+    ///
+    /// The cache is shared with every clone of the client. Warming it again replaces it
+    /// with a fresh copy.
+    pub async fn warm_sets(&self) -> Result<(), APIError> {
+        let body = check_for_empty(self.sets_all().await?).await?;
+        *self.sets_cache.write().unwrap() = body;
+        Ok(())
+    }
+
+    /// Body of the sets endpoint, from the cache if [`MtgClient::warm_sets`] has filled it
+    ///
+    /// Without a warmed cache each call sends a request, as [`check_for_empty`] would.
+    pub async fn sets_body(&self) -> Result<Option<String>, APIError> {
+        let cached = self.sets_cache.read().unwrap().clone();
+        match cached {
+            Some(body) => Ok(Some(body)),
+            None => check_for_empty(self.sets_all().await?).await,
+        }
+    }
+
+    /// Open a random booster pack of the set with the given code, e.g. `"KTK"`
+    ///
+    /// Errors with [`APIError::NotFound`] if there is no set with that code.
+    pub async fn generate_booster(&self, set_code: &str) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!(
+            "{}/{}/booster",
+            self.sets_url(),
+            encode_segment(&set_code.trim().to_uppercase())
+        );
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Download the body of any URL, such as the `imageUrl` of a card
+    pub async fn download(&self, url: &str) -> Result<Vec<u8>, APIError> {
+        // Perform the GET request
+        let response = self.get_request(url).await?;
+        Ok(response.bytes().await?.to_vec())
+    }
+
+    /// Get every card type, e.g. `Creature`
+    pub async fn types_all(&self) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!("{}/types", self.base_url);
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Get every subtype, e.g. `Human`
+    pub async fn subtypes_all(&self) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!("{}/subtypes", self.base_url);
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Get every supertype, e.g. `Legendary`
+    pub async fn supertypes_all(&self) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!("{}/supertypes", self.base_url);
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Get every game format, e.g. `Standard`
+    pub async fn formats_all(&self) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!("{}/formats", self.base_url);
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Get a page of [`MAX_PAGE_SIZE`] cards
+    pub async fn card_page(&self, page_number: &str) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = page_url(&self.cards_url(), page_number, MAX_PAGE_SIZE);
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Get a page of [`MAX_PAGE_SIZE`] cards sorted by a field, e.g. `"name"` or `"cmc"`
+    pub async fn card_page_ordered(
+        &self,
+        page_number: &str,
+        order_by: &str,
+    ) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = format!(
+            "{}&orderBy={}",
+            page_url(&self.cards_url(), page_number, MAX_PAGE_SIZE),
+            encode(order_by)
+        );
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+
+    /// Get a page of at most `page_size` cards, sizes above [`MAX_PAGE_SIZE`] are clamped to it
+    ///
+    /// See [`crate::page_size_clamped`] to check a size before sending it.
+    pub async fn card_page_sized(
+        &self,
+        page_number: &str,
+        page_size: usize,
+    ) -> Result<Response, APIError> {
+        // Define the URL for the API endpoint
+        let url = page_url(&self.cards_url(), page_number, page_size);
+
+        // Perform the GET request
+        self.get_request(&url).await
+    }
+}
+
+/// How long to wait before retrying a rate limited response
+fn retry_delay(response: &Response, attempt: u32) -> Duration {
+    retry_after(response.headers())
+        .map(Duration::from_secs)
+        .unwrap_or(Duration::from_secs(1 << attempt.min(6)))
+}
+
+/// The client behind the free functions, created on first use
+pub(crate) fn shared() -> &'static MtgClient {
+    static SHARED: OnceLock<MtgClient> = OnceLock::new();
+    SHARED.get_or_init(MtgClient::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check_for_empty;
+    use crate::mock::{json, respond, serve, stall};
+
+    fn rate_limited() -> String {
+        respond("429 Too Many Requests", &[("retry-after", "0")], "")
+    }
+
+    #[tokio::test]
+    async fn retry_rate_limited() {
+        let server = serve(vec![rate_limited(), json("{\"cards\":[]}")]).await;
+        let client = MtgClient::new().with_max_retries(2);
+        let res = client.get_request(&server.url).await;
+        assert!(res.is_ok());
+        assert_eq!(server.hits(), 2);
+
+        // Without retries the rate limit is surfaced straight away
+        let server = serve(vec![rate_limited(), json("{\"cards\":[]}")]).await;
+        match MtgClient::new().get_request(&server.url).await {
+            Err(APIError::RateLimited { retry_after }) => assert_eq!(retry_after, Some(0)),
+            other => panic!("Expected RateLimited, got {:?}", other),
+        }
+        assert_eq!(server.hits(), 1);
+    }
+
+    #[tokio::test]
+    async fn share_request_budget() {
+        let server = serve(vec![rate_limited(), rate_limited(), json("{\"cards\":[]}")]).await;
+        let client = MtgClient::new().with_max_retries(5).with_request_budget(2);
+        match client.get_request(&server.url).await {
+            Err(APIError::BudgetExhausted {}) => (),
+            other => panic!("Expected BudgetExhausted, got {:?}", other),
+        }
+        assert_eq!(server.hits(), 2);
+
+        // Clones spend from and refill the same budget
+        let clone = client.clone();
+        assert_eq!(clone.remaining_budget(), Some(0));
+        clone.reset_budget();
+        assert_eq!(client.remaining_budget(), Some(2));
+        assert!(client.get_request(&server.url).await.is_ok());
+        assert_eq!(clone.remaining_budget(), Some(1));
+
+        assert_eq!(MtgClient::new().remaining_budget(), None);
+    }
+
+    #[tokio::test]
+    async fn share_sets_cache() {
+        let sets = || json("{\"sets\":[{\"code\":\"KTK\"}]}");
+        let server = serve(vec![sets(), sets()]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+
+        // A cold cache fetches every time
+        client.sets_body().await.unwrap();
+        assert_eq!(server.hits(), 1);
+
+        client.warm_sets().await.unwrap();
+        let clone = client.clone();
+        assert_eq!(
+            clone.sets_body().await.unwrap().unwrap(),
+            "{\"sets\":[{\"code\":\"KTK\"}]}"
+        );
+        assert!(client.sets_body().await.is_ok());
+        assert_eq!(server.paths(), vec!["/sets", "/sets"]);
+    }
+
+    #[tokio::test]
+    async fn detect_redirects() {
+        let moved = || {
+            respond(
+                "301 Moved Permanently",
+                &[("location", "https://example.com/v2/cards")],
+                "",
+            )
+        };
+        let server = serve(vec![moved()]).await;
+        let client = MtgClient::new()
+            .with_timeout(Duration::from_secs(5))
+            .follow_redirects(false);
+        match client.get_request(&server.url).await {
+            Err(APIError::Redirected { location }) => {
+                assert_eq!(location, "https://example.com/v2/cards")
+            }
+            other => panic!("Expected Redirected, got {:?}", other),
+        }
+
+        // Redirects are followed by default
+        let target = serve(vec![json("{\"cards\":[]}")]).await;
+        let redirect = respond("302 Found", &[("location", &target.url)], "");
+        let server = serve(vec![redirect]).await;
+        assert!(MtgClient::new().get_request(&server.url).await.is_ok());
+        assert_eq!(target.paths(), vec!["/"]);
+    }
+
+    #[tokio::test]
+    async fn time_out_slow_server() {
+        let url = stall().await;
+        let client = MtgClient::new().with_timeout(Duration::from_millis(100));
+        match client.get_request(&url).await {
+            Err(APIError::Timeout {}) => (),
+            other => panic!("Expected Timeout, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn refused_connection() {
+        // Bind then drop a listener so nothing is listening on its port
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        match MtgClient::new().get_request(&url).await {
+            Err(APIError::Connect { .. }) => (),
+            other => panic!("Expected Connect, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn not_found_status() {
+        let missing = respond("404 Not Found", &[], "");
+        let broken = respond("500 Internal Server Error", &[], "");
+        let server = serve(vec![missing, broken]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        match client.card_id_info("999999999").await {
+            Err(APIError::NotFound {}) => (),
+            other => panic!("Expected NotFound, got {:?}", other),
+        }
+        match client.card_id_info("999999999").await {
+            Err(APIError::FailedRequest { status }) => {
+                assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR)
+            }
+            other => panic!("Expected FailedRequest, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn override_base_url() {
+        let server = serve(vec![json("{\"card\":{\"name\":\"Mock\"}}")]).await;
+        let client = MtgClient::new().with_base_url(&format!("{}/", server.url));
+        let res = client.card_id_info("386616").await.unwrap();
+        assert!(check_for_empty(res).await.unwrap().is_some());
+        assert_eq!(server.paths(), vec!["/cards/386616"]);
+    }
+
+    #[tokio::test]
+    async fn encode_rarity() {
+        let server = serve(vec![json("{\"cards\":[]}")]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        client.card_by_rarity("Basic Land").await.unwrap();
+        assert_eq!(server.paths(), vec!["/cards?rarity=Basic+Land"]);
+    }
+
+    #[tokio::test]
+    async fn join_type_line_queries() {
+        let empty = || json("{\"cards\":[]}");
+        let server = serve(vec![empty(), empty()]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        client
+            .card_by_subtypes(&["Goblin", "Warrior"])
+            .await
+            .unwrap();
+        client.card_by_supertypes(&["Legendary"]).await.unwrap();
+        assert_eq!(
+            server.paths(),
+            vec![
+                "/cards?subtypes=Goblin%2CWarrior",
+                "/cards?supertypes=Legendary"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn encode_raw_search() {
+        let empty = || json("{\"cards\":[]}");
+        let server = serve(vec![empty(), empty()]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let params = [("name", "Narset, Enlightened"), ("pageSize", "1")];
+        client.card_search_raw(&params).await.unwrap();
+        client.card_search_raw(&[]).await.unwrap();
+        assert_eq!(
+            server.paths(),
+            vec!["/cards?name=Narset%2C+Enlightened&pageSize=1", "/cards"]
+        );
+    }
+
+    #[tokio::test]
+    async fn encode_names_and_ids() {
+        let fire_ice = json("{\"cards\":[{\"name\":\"Fire // Ice\"}]}");
+        let card = json("{\"card\":{\"name\":\"Fire // Ice\"}}");
+        let server = serve(vec![fire_ice, card]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        client.card_exact_name_info("Fire // Ice").await.unwrap();
+        client.card_id_info("a b/c").await.unwrap();
+        assert_eq!(
+            server.paths(),
+            vec!["/cards?name=%22Fire+%2F%2F+Ice%22", "/cards/a%20b%2Fc"]
+        );
+    }
+
+    #[tokio::test]
+    async fn normalize_set_queries() {
+        let empty = || json("{\"cards\":[]}");
+        let server = serve(vec![empty(), empty()]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        client.card_by_set("ktk").await.unwrap();
+        client.card_by_set_name("Khans of Tarkir").await.unwrap();
+        assert_eq!(
+            server.paths(),
+            vec!["/cards?set=KTK", "/cards?setName=Khans+of+Tarkir"]
+        );
+    }
+
+    #[tokio::test]
+    async fn booster_of_unknown_set() {
+        let server = serve(vec![respond("404 Not Found", &[], "")]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        match client.generate_booster("zzz").await {
+            Err(APIError::NotFound {}) => (),
+            other => panic!("Expected NotFound, got {:?}", other),
+        }
+        assert_eq!(server.paths(), vec!["/sets/ZZZ/booster"]);
+    }
+
+    #[tokio::test]
+    async fn download_bytes() {
+        let png = respond("200 OK", &[("content-type", "image/png")], "PNG");
+        let server = serve(vec![png]).await;
+        let bytes = MtgClient::new().download(&server.url).await.unwrap();
+        assert_eq!(bytes, b"PNG");
+    }
+
+    #[tokio::test]
+    async fn encode_artist() {
+        let server = serve(vec![json("{\"cards\":[]}")]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        client
+            .card_by_artist("Zoltan Boros & Gábor Szikszai")
+            .await
+            .unwrap();
+        assert_eq!(
+            server.paths(),
+            vec!["/cards?artist=Zoltan+Boros+%26+G%C3%A1bor+Szikszai"]
+        );
+    }
+
+    #[tokio::test]
+    async fn unknown_name_in_language() {
+        let empty = respond(
+            "200 OK",
+            &[("content-type", "application/json"), ("count", "0")],
+            "{\"cards\":[]}",
+        );
+        let server = serve(vec![empty]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        match client.card_by_name_in_language("Nichtsda", "German").await {
+            Err(APIError::NoSuchCardName { name }) => assert_eq!(name, "Nichtsda"),
+            other => panic!("Expected NoSuchCardName, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn encode_language_search() {
+        let server = serve(vec![json("{\"cards\":[]}")]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        client
+            .card_by_name_in_language("Éclair de foudre", "French")
+            .await
+            .unwrap();
+        assert_eq!(
+            server.paths(),
+            vec!["/cards?name=%C3%89clair+de+foudre&language=French"]
+        );
+    }
+
+    #[tokio::test]
+    async fn order_page() {
+        let server = serve(vec![json("{\"cards\":[]}")]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        client.card_page_ordered("3", "cmc").await.unwrap();
+        assert_eq!(
+            server.paths(),
+            vec!["/cards?page=3&pageSize=100&orderBy=cmc"]
+        );
+    }
+
+    #[tokio::test]
+    async fn cmc_range_constraints() {
+        let empty = || json("{\"cards\":[]}");
+        let server = serve(vec![empty(), empty(), empty(), empty(), empty()]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        client.card_by_cmc_range(Some(1.0), None).await.unwrap();
+        client.card_by_cmc_range(None, Some(0.5)).await.unwrap();
+        client
+            .card_by_cmc_range(Some(3.0), Some(3.0))
+            .await
+            .unwrap();
+        client.card_by_cmc_range(None, None).await.unwrap();
+        // Only the upper of two unequal bounds is sent
+        client
+            .card_by_cmc_range(Some(1.0), Some(2.0))
+            .await
+            .unwrap();
+        assert_eq!(
+            server.paths(),
+            vec![
+                "/cards?cmc=gte1",
+                "/cards?cmc=lte0.5",
+                "/cards?cmc=3",
+                "/cards",
+                "/cards?cmc=lte2"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn reuse_client() {
+        let client = MtgClient::new();
+
+        let first = client.card_id_info("386616").await;
+        assert!(first.is_ok());
+        assert!(check_for_empty(first.unwrap()).await.unwrap().is_some());
+
+        let second = client
+            .card_exact_name_info("Narset, Enlightened Master")
+            .await;
+        assert!(second.is_ok());
+        assert!(check_for_empty(second.unwrap()).await.unwrap().is_some());
+    }
+}
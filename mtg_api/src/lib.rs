@@ -2,11 +2,39 @@
 //!
 //! See: https://docs.magicthegathering.io/
 #![deny(missing_docs)]
-use reqwest::{Response, StatusCode};
+use reqwest::{
+    header::{HeaderMap, CONTENT_TYPE, LOCATION, RETRY_AFTER},
+    Response, StatusCode,
+};
 use thiserror::Error;
 
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod client;
+#[cfg(any(test, feature = "test-support"))]
+pub mod mock;
+mod query;
+mod schema;
+
+pub use client::MtgClient;
+pub use query::CardQuery;
+pub use schema::{schema_check, FieldKind, SchemaReport};
+
 /// Base URL of the REST API
-const CARDS_URL: &str = "https://api.magicthegathering.io/v1/cards";
+pub const BASE_URL: &str = "https://api.magicthegathering.io/v1";
+
+/// Metadata of every set, such as its code, name and release date
+pub const SETS_URL: &str = "https://api.magicthegathering.io/v1/sets";
+
+/// Largest page size the API will return, anything above this is clamped by the API
+pub const MAX_PAGE_SIZE: usize = 100;
+
+/// Whether a page size is above [`MAX_PAGE_SIZE`], so the crate will ask for fewer cards
+///
+/// Nothing is printed when a size is clamped, check with this to warn about it instead.
+pub fn page_size_clamped(page_size: usize) -> bool {
+    page_size > MAX_PAGE_SIZE
+}
 
 /// Errors generated while getting data from MTG api
 #[derive(Clone, Debug, Error)]
@@ -17,6 +45,15 @@ pub enum APIError {
         /// The status code returned by the request
         status: StatusCode,
     },
+    #[error("Rate limited by the API")]
+    /// When the request fails with `429 Too Many Requests` after any retries
+    RateLimited {
+        /// Seconds the API asked to wait before retrying, from its `Retry-After` header
+        retry_after: Option<u64>,
+    },
+    #[error("Nothing was found at the requested URL")]
+    /// When the request fails with `404 Not Found`, such as for an unknown card id
+    NotFound {},
     #[error("Wrapped Reqwest Error: {e}")]
     /// Contain other misc errors from [`reqwest`] crate
     WrappedReqwest {
@@ -29,69 +66,420 @@ pub enum APIError {
         /// The name searched for
         name: String,
     },
+    #[error("Expected a JSON response but got content type: {got}")]
+    /// When the response is not JSON, e.g. an HTML page from a misconfigured proxy
+    UnexpectedContentType {
+        /// The content type of the response, empty if none was given
+        got: String,
+    },
+    #[error("Request timed out")]
+    /// When the server took longer to respond than the timeout set on the client
+    Timeout {},
+    #[error("Malformed JSON: {e}")]
+    /// When the body of a response can't be parsed as JSON
+    MalformedJson {
+        /// The wrapped parsing error
+        e: String,
+    },
+    #[error("Redirected to: {location}")]
+    /// When the API answers with a redirect and the client does not follow redirects
+    Redirected {
+        /// Where the endpoint has moved to, from the `Location` header
+        location: String,
+    },
+    #[error("Request budget exhausted")]
+    /// When a client set up with a request budget has already spent all of it
+    BudgetExhausted {},
+    #[error("Unsupported query: {reason}")]
+    /// When a search asks for something the API can't express, so no request is sent
+    UnsupportedQuery {
+        /// Why the search can't be sent
+        reason: String,
+    },
+    #[error("Could not connect to the server: {e}")]
+    /// When no connection could be made, e.g. when offline or the host is unreachable
+    Connect {
+        /// The wrapped connection error
+        e: String,
+    },
+    #[error("Could not decode the response body: {e}")]
+    /// When the body of a response could not be decoded by [`reqwest`]
+    Decode {
+        /// The wrapped decoding error
+        e: String,
+    },
 }
 
 impl From<reqwest::Error> for APIError {
     fn from(value: reqwest::Error) -> Self {
-        APIError::WrappedReqwest {
+        let e = value.to_string();
+        if value.is_timeout() {
+            APIError::Timeout {}
+        } else if value.is_connect() {
+            APIError::Connect { e }
+        } else if value.is_decode() {
+            APIError::Decode { e }
+        } else {
+            APIError::WrappedReqwest { e }
+        }
+    }
+}
+
+impl From<serde_json::Error> for APIError {
+    fn from(value: serde_json::Error) -> Self {
+        APIError::MalformedJson {
             e: value.to_string(),
         }
     }
 }
 
+impl APIError {
+    /// The HTTP status code the API responded with, if the error came from one
+    pub fn status_code(&self) -> Option<StatusCode> {
+        match self {
+            APIError::FailedRequest { status } => Some(*status),
+            APIError::RateLimited { .. } => Some(StatusCode::TOO_MANY_REQUESTS),
+            APIError::NotFound {} => Some(StatusCode::NOT_FOUND),
+            _ => None,
+        }
+    }
+
+    /// Describe the error for a person, with a hint on how to resolve it
+    pub fn user_message(&self) -> String {
+        match self {
+            APIError::RateLimited {
+                retry_after: Some(n),
+            } => format!("You've hit the hourly request limit; retry after {n} seconds"),
+            APIError::RateLimited { retry_after: None } => {
+                "You've hit the hourly request limit; wait a while before retrying".to_owned()
+            }
+            APIError::FailedRequest { status } if status.is_server_error() => {
+                format!("The MTG API is having problems ({status}); try again later")
+            }
+            APIError::FailedRequest { status } => {
+                format!("The MTG API rejected the request ({status}); check the search terms")
+            }
+            APIError::NotFound {} => {
+                "Nothing was found at that address; check the card id or set code is correct"
+                    .to_owned()
+            }
+            APIError::WrappedReqwest { e } => {
+                format!("The request to the MTG API failed; try again later ({e})")
+            }
+            APIError::NoSuchCardName { name } => {
+                format!("No card is named \"{name}\"; check spelling or try a partial search")
+            }
+            APIError::UnexpectedContentType { got } => {
+                format!("Expected card data but received \"{got}\"; check any proxy settings")
+            }
+            APIError::Timeout {} => {
+                "The MTG API took too long to respond; try again or raise the timeout".to_owned()
+            }
+            APIError::MalformedJson { e } | APIError::Decode { e } => {
+                format!("The MTG API sent data that could not be read ({e})")
+            }
+            APIError::Redirected { location } => {
+                format!("The MTG API has moved this endpoint to {location}; update the base URL")
+            }
+            APIError::BudgetExhausted {} => {
+                "This batch has used up its request budget; reset or raise the budget".to_owned()
+            }
+            APIError::UnsupportedQuery { reason } => {
+                format!("The MTG API can't answer this search, {reason}; narrow the search")
+            }
+            APIError::Connect { e } => {
+                format!("Could not connect to the MTG API; check your internet connection ({e})")
+            }
+        }
+    }
+}
+
 /// Check if there are cards returned in the response
+///
+/// Errors if the response is not JSON.
 pub async fn check_for_empty(res: Response) -> Result<Option<String>, APIError> {
-    let text = res.text().await?;
-    if text == "{\"cards\":[]}" {
-        Ok(None)
-    } else {
-        Ok(Some(text))
-    }
+    Ok(split_response(res).await?.1)
 }
 
-async fn get_request(url: &str) -> Result<Response, APIError> {
-    // Perform the GET request
-    let response = reqwest::get(url).await?;
+/// Like [`check_for_empty`] but also hands back the headers of the response
+///
+/// Reading the body consumes the response, so this lets headers such as `Total-Count`
+/// be used along with the body without making a second request.
+pub async fn split_response(res: Response) -> Result<(HeaderMap, Option<String>), APIError> {
+    let content_type = res
+        .headers()
+        .get(CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if !content_type.starts_with("application/json") {
+        return Err(APIError::UnexpectedContentType {
+            got: content_type.to_owned(),
+        });
+    }
 
-    // Check if the request was successful
-    match response.status().is_success() {
-        true => Ok(response),
-        false => Err(APIError::FailedRequest {
-            status: response.status(),
-        }),
+    let headers = res.headers().clone();
+    let text = res.text().await?;
+    match text == "{\"cards\":[]}" {
+        true => Ok((headers, None)),
+        false => Ok((headers, Some(text))),
     }
 }
 
 /// Find a card by its numerical ID
 pub async fn card_id_info(card_id: &str) -> Result<Response, APIError> {
-    // Define the URL for the API endpoint
-    let url = format!("{}/{}", CARDS_URL, card_id);
-
-    // Perform the GET request
-    get_request(&url).await
+    client::shared().card_id_info(card_id).await
 }
 
 /// Find a card by its exact name
+///
+/// Errors with [`APIError::NoSuchCardName`] if no card has that name.
 pub async fn card_exact_name_info(card_name: &str) -> Result<Response, APIError> {
-    // Define the URL for the API endpoint
-    let url = format!("{}?name=\"{}\"", CARDS_URL, card_name);
+    client::shared().card_exact_name_info(card_name).await
+}
+
+/// Find cards by their Gatherer multiverse id
+pub async fn card_by_multiverseid(id: u64) -> Result<Response, APIError> {
+    client::shared().card_by_multiverseid(id).await
+}
+
+/// Find every card whose name contains the fragment
+///
+/// Errors with [`APIError::NoSuchCardName`] if no card matches.
+pub async fn card_name_contains(fragment: &str) -> Result<Response, APIError> {
+    client::shared().card_name_contains(fragment).await
+}
+
+/// Find cards by their name in another language, e.g. `("Blitzschlag", "German")`
+///
+/// Errors with [`APIError::NoSuchCardName`] if no card has that name in the language.
+pub async fn card_by_name_in_language(name: &str, language: &str) -> Result<Response, APIError> {
+    client::shared()
+        .card_by_name_in_language(name, language)
+        .await
+}
 
-    // Perform the GET request
-    get_request(&url).await
+/// Find cards which are all of the given colors, e.g. `["red", "white"]`
+///
+/// Colors are case-insensitive and an empty slice returns all cards.
+pub async fn card_by_colors(colors: &[&str]) -> Result<Response, APIError> {
+    client::shared().card_by_colors(colors).await
 }
 
-/// Get a page of cards
+/// Find cards which have all of the given types, e.g. `["Artifact", "Creature"]`
+pub async fn card_by_types(types: &[&str]) -> Result<Response, APIError> {
+    client::shared().card_by_types(types).await
+}
+
+/// Find cards which have all of the given subtypes, e.g. `["Goblin", "Warrior"]`
+pub async fn card_by_subtypes(subtypes: &[&str]) -> Result<Response, APIError> {
+    client::shared().card_by_subtypes(subtypes).await
+}
+
+/// Find cards which have all of the given supertypes, e.g. `["Legendary"]`
+pub async fn card_by_supertypes(supertypes: &[&str]) -> Result<Response, APIError> {
+    client::shared().card_by_supertypes(supertypes).await
+}
+
+/// Find cards with a converted mana cost of at least `min` or at most `max`
+///
+/// The API reads one `cmc` value per query. Equal bounds find cards of exactly that cost,
+/// other pairs send only `max`, so cards below `min` are left for the caller to filter out.
+/// Leaving out both returns every card.
+pub async fn card_by_cmc_range(min: Option<f32>, max: Option<f32>) -> Result<Response, APIError> {
+    client::shared().card_by_cmc_range(min, max).await
+}
+
+/// Search cards with query parameters not otherwise wrapped, e.g. `[("gameFormat", "Modern")]`
+///
+/// Keys and values are URL-encoded, no parameters returns every card.
+pub async fn card_search_raw(params: &[(&str, &str)]) -> Result<Response, APIError> {
+    client::shared().card_search_raw(params).await
+}
+
+/// Find cards of the given rarity, e.g. `"Mythic"` or `"Basic Land"`
+pub async fn card_by_rarity(rarity: &str) -> Result<Response, APIError> {
+    client::shared().card_by_rarity(rarity).await
+}
+
+/// Find cards printed in the set with the given code, e.g. `"KTK"`
+///
+/// The code is case-insensitive.
+pub async fn card_by_set(set_code: &str) -> Result<Response, APIError> {
+    client::shared().card_by_set(set_code).await
+}
+
+/// Find cards printed in the set with the given name, e.g. `"Khans of Tarkir"`
+pub async fn card_by_set_name(name: &str) -> Result<Response, APIError> {
+    client::shared().card_by_set_name(name).await
+}
+
+/// Find cards whose rules text contains the fragment, e.g. `"draw a card"`
+pub async fn card_by_text(fragment: &str) -> Result<Response, APIError> {
+    client::shared().card_by_text(fragment).await
+}
+
+/// Find cards whose flavor text contains the fragment
+pub async fn card_by_flavor(fragment: &str) -> Result<Response, APIError> {
+    client::shared().card_by_flavor(fragment).await
+}
+
+/// Find cards illustrated by the given artist, e.g. `"Rebecca Guay"`
+pub async fn card_by_artist(artist: &str) -> Result<Response, APIError> {
+    client::shared().card_by_artist(artist).await
+}
+
+/// Find cards with the given legality in a format, e.g. `("Commander", "Legal")`
+pub async fn card_by_legality(format: &str, legality: &str) -> Result<Response, APIError> {
+    client::shared().card_by_legality(format, legality).await
+}
+
+/// Get the metadata of every set
+pub async fn sets_all() -> Result<Response, APIError> {
+    client::shared().sets_all().await
+}
+
+/// Fetch the metadata of every set and keep it for [`sets_body`], see [`MtgClient::warm_sets`]
+pub async fn warm_sets() -> Result<(), APIError> {
+    client::shared().warm_sets().await
+}
+
+/// Body of the sets endpoint, from the cache if [`warm_sets`] has filled it
+pub async fn sets_body() -> Result<Option<String>, APIError> {
+    client::shared().sets_body().await
+}
+
+/// Open a random booster pack of the set with the given code, e.g. `"KTK"`
+///
+/// Errors with [`APIError::NotFound`] if there is no set with that code.
+pub async fn generate_booster(set_code: &str) -> Result<Response, APIError> {
+    client::shared().generate_booster(set_code).await
+}
+
+/// Download the body of any URL, such as the `imageUrl` of a card
+pub async fn download(url: &str) -> Result<Vec<u8>, APIError> {
+    client::shared().download(url).await
+}
+
+/// Get every card type, e.g. `Creature`
+pub async fn types_all() -> Result<Response, APIError> {
+    client::shared().types_all().await
+}
+
+/// Get every subtype, e.g. `Human`
+pub async fn subtypes_all() -> Result<Response, APIError> {
+    client::shared().subtypes_all().await
+}
+
+/// Get every supertype, e.g. `Legendary`
+pub async fn supertypes_all() -> Result<Response, APIError> {
+    client::shared().supertypes_all().await
+}
+
+/// Get every game format, e.g. `Standard`
+pub async fn formats_all() -> Result<Response, APIError> {
+    client::shared().formats_all().await
+}
+
+/// Error with [`APIError::NoSuchCardName`] if the `Count` header says no cards were returned
+pub(crate) fn require_cards(res: Response, name: &str) -> Result<Response, APIError> {
+    check_count(res.headers(), name)?;
+    Ok(res)
+}
+
+/// Error unless the status is a success, with [`APIError::NotFound`],
+/// [`APIError::RateLimited`] and [`APIError::Redirected`] kept apart from the rest
+pub(crate) fn check_status(status: StatusCode, headers: &HeaderMap) -> Result<(), APIError> {
+    match status {
+        StatusCode::NOT_FOUND => Err(APIError::NotFound {}),
+        StatusCode::TOO_MANY_REQUESTS => Err(APIError::RateLimited {
+            retry_after: retry_after(headers),
+        }),
+        s if s.is_redirection() => Err(APIError::Redirected {
+            location: headers
+                .get(LOCATION)
+                .and_then(|v| v.to_str().ok())
+                .unwrap_or_default()
+                .to_owned(),
+        }),
+        s if s.is_success() => Ok(()),
+        status => Err(APIError::FailedRequest { status }),
+    }
+}
+
+/// Seconds to wait given by a `Retry-After` header, if it holds a number of seconds
+pub(crate) fn retry_after(headers: &HeaderMap) -> Option<u64> {
+    headers
+        .get(RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+}
+
+/// Check the `Count` header of a response, shared by the async and blocking requests
+pub(crate) fn check_count(headers: &HeaderMap, name: &str) -> Result<(), APIError> {
+    match headers.get("Count").map(|c| c.as_bytes()) {
+        Some(b"0") => Err(APIError::NoSuchCardName {
+            name: name.to_owned(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Percent-encode a value for use in a query string
+pub(crate) fn encode(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+/// Percent-encode a value for use as one segment of a URL path
+///
+/// Unlike a query string, `+` does not stand for a space in a path.
+pub(crate) fn encode_segment(value: &str) -> String {
+    encode(value).replace('+', "%20")
+}
+
+/// The query string value searching for exactly the given card name
+pub(crate) fn exact_name(name: &str) -> String {
+    encode(&format!("\"{}\"", name))
+}
+
+/// Build the URL for a page of cards, never asking for more than [`MAX_PAGE_SIZE`]
+///
+/// Larger sizes are clamped to [`MAX_PAGE_SIZE`], as the API would do the same, matching
+/// [`CardQuery::page_size`]. Whether a size will be clamped is told by [`page_size_clamped`].
+pub(crate) fn page_url(cards_url: &str, page_number: &str, page_size: usize) -> String {
+    let page_size = page_size.min(MAX_PAGE_SIZE);
+    format!(
+        "{}?page={}&pageSize={}",
+        cards_url,
+        encode(page_number),
+        page_size
+    )
+}
+
+/// Get a page of [`MAX_PAGE_SIZE`] cards
 pub async fn card_page(page_number: &str) -> Result<Response, APIError> {
-    // Define the URL for the API endpoint
-    let url = format!("{}?page={}", CARDS_URL, page_number);
+    client::shared().card_page(page_number).await
+}
 
-    // Perform the GET request
-    get_request(&url).await
+/// Get a page of [`MAX_PAGE_SIZE`] cards sorted by a field, e.g. `"name"` or `"cmc"`
+pub async fn card_page_ordered(page_number: &str, order_by: &str) -> Result<Response, APIError> {
+    client::shared()
+        .card_page_ordered(page_number, order_by)
+        .await
+}
+
+/// Get a page of at most `page_size` cards, sizes above [`MAX_PAGE_SIZE`] are clamped to it
+///
+/// See [`page_size_clamped`] to check a size before sending it.
+pub async fn card_page_sized(page_number: &str, page_size: usize) -> Result<Response, APIError> {
+    client::shared()
+        .card_page_sized(page_number, page_size)
+        .await
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mock::{json, respond, serve};
 
     #[tokio::test]
     async fn fetch_id_result() {
@@ -110,9 +498,241 @@ mod tests {
         let exact_pass_res = exact_pass.unwrap();
         assert!(check_for_empty(exact_pass_res).await.unwrap().is_some());
         // Check internal err
-        assert!(exact_fail.is_ok());
-        let exact_fail_res = exact_fail.unwrap();
-        assert!(check_for_empty(exact_fail_res).await.unwrap().is_none());
+        match exact_fail {
+            Err(APIError::NoSuchCardName { name }) => {
+                assert_eq!(name, "Narset, Unelightned Student")
+            }
+            other => panic!("Expected NoSuchCardName, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn reject_wrong_content_type() {
+        let html = respond("200 OK", &[("content-type", "text/html")], "<html></html>");
+        let server = serve(vec![html]).await;
+        let res = MtgClient::new().get_request(&server.url).await.unwrap();
+        match check_for_empty(res).await {
+            Err(APIError::UnexpectedContentType { got }) => assert_eq!(got, "text/html"),
+            other => panic!("Expected UnexpectedContentType, got {:?}", other),
+        }
+
+        let utf8 = respond(
+            "200 OK",
+            &[("content-type", "application/json; charset=utf-8")],
+            "{\"cards\":[]}",
+        );
+        let server = serve(vec![utf8]).await;
+        let res = MtgClient::new().get_request(&server.url).await.unwrap();
+        assert!(check_for_empty(res).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn keep_headers_with_body() {
+        let page = respond(
+            "200 OK",
+            &[("content-type", "application/json"), ("total-count", "2")],
+            "{\"cards\":[{\"name\":\"A\"},{\"name\":\"B\"}]}",
+        );
+        let server = serve(vec![page]).await;
+        let res = MtgClient::new().get_request(&server.url).await.unwrap();
+        let (headers, body) = split_response(res).await.unwrap();
+        assert_eq!(headers["total-count"], "2");
+        assert!(body.unwrap().contains("\"B\""));
+    }
+
+    #[tokio::test]
+    async fn fetch_name_contains_result() {
+        let narsets = card_name_contains("Narset").await;
+        assert!(narsets.is_ok());
+        let json = check_for_empty(narsets.unwrap()).await.unwrap().unwrap();
+        let body: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let mut names: Vec<&str> = body["cards"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|c| c["name"].as_str().unwrap())
+            .collect();
+        names.sort();
+        names.dedup();
+        assert!(names.len() > 1);
+        assert!(names.iter().all(|n| n.contains("Narset")));
+
+        match card_name_contains("Xyzzyplugh").await {
+            Err(APIError::NoSuchCardName { name }) => assert_eq!(name, "Xyzzyplugh"),
+            other => panic!("Expected NoSuchCardName, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn empty_count_is_no_such_name() {
+        let empty = respond(
+            "200 OK",
+            &[("content-type", "application/json"), ("count", "0")],
+            "{\"cards\":[]}",
+        );
+        let server = serve(vec![empty]).await;
+        let res = MtgClient::new().get_request(&server.url).await.unwrap();
+        assert!(matches!(
+            require_cards(res, "Nobody"),
+            Err(APIError::NoSuchCardName { .. })
+        ));
+
+        let server = serve(vec![json("{\"cards\":[{\"name\":\"A\"}]}")]).await;
+        let res = MtgClient::new().get_request(&server.url).await.unwrap();
+        assert!(require_cards(res, "A").is_ok());
+    }
+
+    #[tokio::test]
+    async fn fetch_colors_result() {
+        let red = card_by_colors(&["red"]).await;
+        assert!(red.is_ok());
+        assert!(check_for_empty(red.unwrap()).await.unwrap().is_some());
+
+        let mixed_case = card_by_colors(&["Red", "WHITE"]).await;
+        assert!(mixed_case.is_ok());
+        assert!(check_for_empty(mixed_case.unwrap())
+            .await
+            .unwrap()
+            .is_some());
+
+        let all = card_by_colors(&[]).await;
+        assert!(all.is_ok());
+        assert!(check_for_empty(all.unwrap()).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn fetch_rarity_result() {
+        let mythics = card_by_rarity("Mythic").await;
+        assert!(mythics.is_ok());
+        let json = check_for_empty(mythics.unwrap()).await.unwrap().unwrap();
+        let body: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let cards = body["cards"].as_array().unwrap();
+        assert!(!cards.is_empty());
+        assert!(cards.iter().all(|c| c["rarity"] == "Mythic"));
+    }
+
+    #[tokio::test]
+    async fn fetch_set_result() {
+        let khans = card_by_set("KTK").await;
+        assert!(khans.is_ok());
+        let json = check_for_empty(khans.unwrap()).await.unwrap().unwrap();
+        let body: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let cards = body["cards"].as_array().unwrap();
+        assert!(cards.iter().all(|c| c["set"] == "KTK"));
+        assert!(cards
+            .iter()
+            .any(|c| c["name"] == "Narset, Enlightened Master"));
+    }
+
+    #[tokio::test]
+    async fn fetch_subtypes_result() {
+        let goblins = card_by_subtypes(&["Goblin"]).await.unwrap();
+        let json = check_for_empty(goblins).await.unwrap().unwrap();
+        let body: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let cards = body["cards"].as_array().unwrap();
+        assert!(!cards.is_empty());
+        assert!(cards
+            .iter()
+            .all(|c| c["subtypes"].as_array().unwrap().contains(&"Goblin".into())));
+    }
+
+    #[tokio::test]
+    async fn fetch_raw_search_result() {
+        let narset = card_search_raw(&[("name", "Narset"), ("pageSize", "1")])
+            .await
+            .unwrap();
+        let json = check_for_empty(narset).await.unwrap().unwrap();
+        let body: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(body["cards"].as_array().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn encode_path_segments() {
+        assert_eq!(encode_segment("Fire // Ice"), "Fire%20%2F%2F%20Ice");
+        assert_eq!(encode_segment("a+b"), "a%2Bb");
+        assert_eq!(exact_name("Fire // Ice"), "%22Fire+%2F%2F+Ice%22");
+    }
+
+    #[test]
+    fn sets_url_under_base() {
+        assert_eq!(SETS_URL, format!("{}/sets", BASE_URL));
+    }
+
+    #[test]
+    fn encode_query_values() {
+        assert_eq!(encode("red,white"), "red%2Cwhite");
+        assert_eq!(encode("Fire // Ice"), "Fire+%2F%2F+Ice");
+        assert_eq!(encode("Narset"), "Narset");
+    }
+
+    #[test]
+    fn page_size_ceiling() {
+        let cards = format!("{}/cards", BASE_URL);
+        assert_eq!(
+            page_url(&cards, "2", MAX_PAGE_SIZE),
+            format!("{}?page=2&pageSize=100", cards)
+        );
+        assert_eq!(
+            page_url(&cards, "1", 20),
+            format!("{}?page=1&pageSize=20", cards)
+        );
+        assert_eq!(
+            page_url(&cards, "1", 500),
+            page_url(&cards, "1", MAX_PAGE_SIZE)
+        );
+
+        assert!(page_size_clamped(500));
+        assert!(page_size_clamped(MAX_PAGE_SIZE + 1));
+        assert!(!page_size_clamped(MAX_PAGE_SIZE));
+        assert!(!page_size_clamped(20));
+    }
+
+    #[test]
+    fn error_status_code() {
+        let server = APIError::FailedRequest {
+            status: StatusCode::BAD_GATEWAY,
+        };
+        assert_eq!(server.status_code(), Some(StatusCode::BAD_GATEWAY));
+        assert_eq!(
+            APIError::NotFound {}.status_code(),
+            Some(StatusCode::NOT_FOUND)
+        );
+        assert_eq!(APIError::Timeout {}.status_code(), None);
+    }
+
+    #[test]
+    fn error_user_message() {
+        let limited = APIError::RateLimited {
+            retry_after: Some(120),
+        };
+        assert_eq!(
+            limited.user_message(),
+            "You've hit the hourly request limit; retry after 120 seconds"
+        );
+        let limited = APIError::RateLimited { retry_after: None };
+        assert!(limited.user_message().contains("request limit"));
+
+        let server = APIError::FailedRequest {
+            status: StatusCode::BAD_GATEWAY,
+        };
+        assert!(server.user_message().contains("502 Bad Gateway"));
+
+        let name = APIError::NoSuchCardName {
+            name: "Narset".to_owned(),
+        };
+        assert_eq!(
+            name.user_message(),
+            "No card is named \"Narset\"; check spelling or try a partial search"
+        );
+
+        let connect = APIError::Connect {
+            e: "refused".to_owned(),
+        };
+        assert!(connect.user_message().contains("internet connection"));
+        let other = APIError::WrappedReqwest {
+            e: "builder".to_owned(),
+        };
+        assert!(!other.user_message().contains("internet connection"));
     }
 
     #[tokio::test]
@@ -0,0 +1,97 @@
+//! A minimal HTTP server for hermetic tests
+//!
+//! Enabled by the `test-support` feature, so dependent crates can test against it too.
+use std::sync::{Arc, Mutex};
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Build a raw HTTP response which closes its connection once sent
+pub fn respond(status: &str, headers: &[(&str, &str)], body: &str) -> String {
+    let mut res = format!("HTTP/1.1 {}\r\n", status);
+    for (name, value) in headers {
+        res += &format!("{}: {}\r\n", name, value);
+    }
+    res += &format!(
+        "content-length: {}\r\nconnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    res
+}
+
+/// A JSON `200 OK` response with the given body
+pub fn json(body: &str) -> String {
+    respond("200 OK", &[("content-type", "application/json")], body)
+}
+
+/// A JSON `200 OK` page of cards with the pagination headers the API sends
+///
+/// `Count` is the number of cards in `body`, the other headers are as given.
+pub fn json_page(body: &str, page_size: usize, total_count: usize) -> String {
+    let value: serde_json::Value = serde_json::from_str(body).unwrap();
+    let count = value["cards"].as_array().map_or(0, Vec::len).to_string();
+    let page_size = page_size.to_string();
+    let total_count = total_count.to_string();
+    let headers = [
+        ("content-type", "application/json"),
+        ("page-size", page_size.as_str()),
+        ("count", count.as_str()),
+        ("total-count", total_count.as_str()),
+        ("ratelimit-limit", "5000"),
+        ("ratelimit-remaining", "4999"),
+    ];
+    respond("200 OK", &headers, body)
+}
+
+/// A local server answering each connection with the next of `responses`
+pub struct MockServer {
+    /// Base URL of the server
+    pub url: String,
+    paths: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockServer {
+    /// Number of requests the server has answered so far
+    pub fn hits(&self) -> usize {
+        self.paths.lock().unwrap().len()
+    }
+
+    /// Path and query of each request received so far
+    pub fn paths(&self) -> Vec<String> {
+        self.paths.lock().unwrap().clone()
+    }
+}
+
+/// Serve the responses in order, one per connection, on a local port
+pub async fn serve(responses: Vec<String>) -> MockServer {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let paths = Arc::new(Mutex::new(Vec::new()));
+    let received = paths.clone();
+    tokio::spawn(async move {
+        for response in responses {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0; 4096];
+            let n = stream.read(&mut buf).await.unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]);
+            let path = request.split(' ').nth(1).unwrap_or_default().to_owned();
+            received.lock().unwrap().push(path);
+            stream.write_all(response.as_bytes()).await.unwrap();
+        }
+    });
+    MockServer { url, paths }
+}
+
+/// A local server which accepts connections but never responds, returning its base URL
+pub async fn stall() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        let mut open = Vec::new();
+        while let Ok((stream, _)) = listener.accept().await {
+            open.push(stream);
+        }
+    });
+    url
+}
@@ -0,0 +1,210 @@
+#![deny(missing_docs)]
+use reqwest::Response;
+
+use crate::{client, encode, APIError, MtgClient, MAX_PAGE_SIZE};
+
+/// A search of the cards endpoint combining several filters
+///
+/// Each filter that is set adds a `key=value` pair to the query string, setting the same
+/// filter again replaces its value. With no filters every card is returned.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), mtg_api::APIError> {
+/// let res = mtg_api::CardQuery::new()
+///     .colors(&["red"])
+///     .supertypes(&["legendary"])
+///     .types(&["creature"])
+///     .send()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct CardQuery<'a> {
+    client: &'a MtgClient,
+    params: Vec<(&'static str, String)>,
+}
+
+impl Default for CardQuery<'static> {
+    fn default() -> Self {
+        CardQuery::with_client(client::shared())
+    }
+}
+
+impl CardQuery<'static> {
+    /// Start a query sent through the shared client used by the free functions
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<'a> CardQuery<'a> {
+    /// Start a query sent through the given client
+    pub(crate) fn with_client(client: &'a MtgClient) -> Self {
+        CardQuery {
+            client,
+            params: Vec::new(),
+        }
+    }
+
+    /// Set a parameter, replacing any earlier value for the same key
+    fn param(mut self, key: &'static str, value: String) -> Self {
+        self.params.retain(|(k, _)| *k != key);
+        self.params.push((key, value));
+        self
+    }
+
+    /// Cards whose name contains the fragment
+    pub fn name(self, name: &str) -> Self {
+        self.param("name", name.to_owned())
+    }
+
+    /// Cards which are all of the given colors, case-insensitive
+    pub fn colors(self, colors: &[&str]) -> Self {
+        self.param("colors", colors.join(",").to_lowercase())
+    }
+
+    /// Cards which have all of the given types, e.g. `["Artifact", "Creature"]`
+    pub fn types(self, types: &[&str]) -> Self {
+        self.param("types", types.join(","))
+    }
+
+    /// Cards which have all of the given supertypes, e.g. `["Legendary"]`
+    pub fn supertypes(self, supertypes: &[&str]) -> Self {
+        self.param("supertypes", supertypes.join(","))
+    }
+
+    /// Cards of the given rarity, e.g. `"Mythic Rare"`
+    pub fn rarity(self, rarity: &str) -> Self {
+        self.param("rarity", rarity.to_owned())
+    }
+
+    /// Cards printed in the set with the given code, e.g. `"KTK"`
+    ///
+    /// The code is case-insensitive, as for [`MtgClient::card_by_set`].
+    pub fn set(self, set: &str) -> Self {
+        self.param("set", set.trim().to_uppercase())
+    }
+
+    /// Cards illustrated by the given artist, e.g. `"Rebecca Guay"`
+    pub fn artist(self, artist: &str) -> Self {
+        self.param("artist", artist.to_owned())
+    }
+
+    /// Cards with a legality in the given format, e.g. `"Pioneer"`, even if banned in it
+    pub fn game_format(self, format: &str) -> Self {
+        self.param("gameFormat", format.to_owned())
+    }
+
+    /// Cards with the given legality, e.g. `"Banned"`, in the [`CardQuery::game_format`]
+    pub fn legality(self, legality: &str) -> Self {
+        self.param("legality", legality.to_owned())
+    }
+
+    /// Cards with exactly the given converted mana cost
+    ///
+    /// The API takes one `cmc` filter per search, so this replaces
+    /// [`CardQuery::cmc_at_least`] and [`CardQuery::cmc_at_most`].
+    pub fn cmc(self, cmc: u32) -> Self {
+        self.param("cmc", cmc.to_string())
+    }
+
+    /// Cards with a converted mana cost of at least `min`, replacing any other `cmc` filter
+    pub fn cmc_at_least(self, min: f32) -> Self {
+        self.param("cmc", format!("gte{}", min))
+    }
+
+    /// Cards with a converted mana cost of at most `max`, replacing any other `cmc` filter
+    pub fn cmc_at_most(self, max: f32) -> Self {
+        self.param("cmc", format!("lte{}", max))
+    }
+
+    /// Which page of results to return, starting from 1
+    pub fn page(self, page: u32) -> Self {
+        self.param("page", page.to_string())
+    }
+
+    /// How many cards to return per page, sizes above [`MAX_PAGE_SIZE`] are clamped to it
+    ///
+    /// See [`crate::page_size_clamped`] to check a size before setting it.
+    pub fn page_size(self, page_size: usize) -> Self {
+        self.param("pageSize", page_size.min(MAX_PAGE_SIZE).to_string())
+    }
+
+    /// Sort the results by a field, e.g. `"name"` or `"cmc"`
+    pub fn order_by(self, field: &str) -> Self {
+        self.param("orderBy", field.to_owned())
+    }
+
+    /// The full URL the query will request, with every value percent-encoded
+    pub fn url(&self) -> String {
+        let query: Vec<String> = self
+            .params
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, encode(value)))
+            .collect();
+        match query.is_empty() {
+            true => self.client.cards_url(),
+            false => format!("{}?{}", self.client.cards_url(), query.join("&")),
+        }
+    }
+
+    /// Perform the search
+    pub async fn send(&self) -> Result<Response, APIError> {
+        self.client.get_request(&self.url()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::check_for_empty;
+    use crate::mock::{json, serve};
+
+    #[test]
+    fn build_query_url() {
+        let cards = format!("{}/cards", crate::BASE_URL);
+        assert_eq!(CardQuery::new().url(), cards);
+
+        let query = CardQuery::new()
+            .name("Fire // Ice")
+            .rarity("Mythic Rare")
+            .page_size(500)
+            .page(2)
+            .name("Bolt")
+            .order_by("cmc")
+            .game_format("Modern")
+            .legality("Banned");
+        assert_eq!(
+            query.url(),
+            format!(
+                "{}?rarity=Mythic+Rare&pageSize=100&page=2&name=Bolt&orderBy=cmc&gameFormat=Modern&legality=Banned",
+                cards
+            )
+        );
+
+        let query = CardQuery::new().cmc(2).cmc_at_most(2.5);
+        assert_eq!(query.url(), format!("{}?cmc=lte2.5", cards));
+        let query = query.cmc_at_least(1.0);
+        assert_eq!(query.url(), format!("{}?cmc=gte1", cards));
+    }
+
+    #[tokio::test]
+    async fn query_red_legendary_creatures() {
+        let server = serve(vec![json("{\"cards\":[{\"name\":\"Zurgo Helmsmasher\"}]}")]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let query = client
+            .query()
+            .colors(&["Red"])
+            .supertypes(&["Legendary"])
+            .types(&["Creature"])
+            .cmc(5)
+            .set(" ktk ");
+        let res = query.send().await.unwrap();
+        assert!(check_for_empty(res).await.unwrap().is_some());
+        assert_eq!(
+            server.paths(),
+            vec!["/cards?colors=red&supertypes=Legendary&types=Creature&cmc=5&set=KTK"]
+        );
+    }
+}
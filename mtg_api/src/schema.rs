@@ -0,0 +1,170 @@
+#![deny(missing_docs)]
+use serde_json::Value;
+
+use crate::{card_id_info, check_for_empty, APIError};
+
+/// Id of the card fetched by [`schema_check`], Narset, Enlightened Master
+const KNOWN_CARD_ID: &str = "386616";
+
+/// Fields the known card is expected to have, with the kind of their values
+const EXPECTED_FIELDS: &[(&str, FieldKind)] = &[
+    ("name", FieldKind::String),
+    ("manaCost", FieldKind::String),
+    ("cmc", FieldKind::Number),
+    ("colors", FieldKind::Array),
+    ("colorIdentity", FieldKind::Array),
+    ("type", FieldKind::String),
+    ("supertypes", FieldKind::Array),
+    ("types", FieldKind::Array),
+    ("subtypes", FieldKind::Array),
+    ("rarity", FieldKind::String),
+    ("set", FieldKind::String),
+    ("setName", FieldKind::String),
+    ("text", FieldKind::String),
+    ("artist", FieldKind::String),
+    ("number", FieldKind::String),
+    ("power", FieldKind::String),
+    ("toughness", FieldKind::String),
+    ("layout", FieldKind::String),
+    ("multiverseid", FieldKind::String),
+    ("imageUrl", FieldKind::String),
+    ("rulings", FieldKind::Array),
+    ("foreignNames", FieldKind::Array),
+    ("printings", FieldKind::Array),
+    ("originalText", FieldKind::String),
+    ("originalType", FieldKind::String),
+    ("legalities", FieldKind::Array),
+    ("id", FieldKind::String),
+];
+
+/// Fields the API only gives some cards, which are not reported as new when present
+const OPTIONAL_FIELDS: &[&str] = &[
+    "flavor",
+    "names",
+    "loyalty",
+    "watermark",
+    "border",
+    "timeshifted",
+    "hand",
+    "life",
+    "reserved",
+    "releaseDate",
+    "starter",
+    "variations",
+];
+
+/// The kind of a JSON value
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldKind {
+    /// A JSON string
+    String,
+    /// A JSON number
+    Number,
+    /// `true` or `false`
+    Bool,
+    /// A JSON array
+    Array,
+    /// A JSON object
+    Object,
+    /// `null`
+    Null,
+}
+
+impl FieldKind {
+    fn of(value: &Value) -> Self {
+        match value {
+            Value::String(_) => FieldKind::String,
+            Value::Number(_) => FieldKind::Number,
+            Value::Bool(_) => FieldKind::Bool,
+            Value::Array(_) => FieldKind::Array,
+            Value::Object(_) => FieldKind::Object,
+            Value::Null => FieldKind::Null,
+        }
+    }
+}
+
+/// Differences between a card returned by the API and the fields this crate expects
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SchemaReport {
+    /// Expected fields which were absent
+    pub missing: Vec<String>,
+    /// Fields which were present but not known to the crate
+    pub new: Vec<String>,
+    /// Fields whose value was not of the expected kind, with the kind that was found
+    pub mistyped: Vec<(String, FieldKind)>,
+}
+
+impl SchemaReport {
+    /// Compare a single card object against the expected fields
+    pub fn from_card(card: &Value) -> Self {
+        let mut report = SchemaReport::default();
+        let empty = serde_json::Map::new();
+        let fields = card.as_object().unwrap_or(&empty);
+
+        for (name, kind) in EXPECTED_FIELDS {
+            match fields.get(*name) {
+                None => report.missing.push(name.to_string()),
+                Some(value) if FieldKind::of(value) != *kind => report
+                    .mistyped
+                    .push((name.to_string(), FieldKind::of(value))),
+                Some(_) => (),
+            }
+        }
+        for name in fields.keys() {
+            let known = EXPECTED_FIELDS.iter().any(|(n, _)| n == name);
+            if !known && !OPTIONAL_FIELDS.contains(&name.as_str()) {
+                report.new.push(name.clone());
+            }
+        }
+        report
+    }
+
+    /// Whether the card matched the expected fields exactly
+    pub fn is_clean(&self) -> bool {
+        self.missing.is_empty() && self.new.is_empty() && self.mistyped.is_empty()
+    }
+}
+
+/// Fetch a known card and report any drift from the fields this crate expects
+pub async fn schema_check() -> Result<SchemaReport, APIError> {
+    let res = card_id_info(KNOWN_CARD_ID).await?;
+    let text = check_for_empty(res).await?.ok_or(APIError::NotFound {})?;
+    let body: Value = serde_json::from_str(&text)?;
+    Ok(SchemaReport::from_card(&body["card"]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_drift() {
+        let mut card = serde_json::Map::new();
+        for (name, kind) in EXPECTED_FIELDS {
+            let value = match kind {
+                FieldKind::Number => serde_json::json!(6),
+                FieldKind::Array => serde_json::json!([]),
+                _ => serde_json::json!(""),
+            };
+            card.insert(name.to_string(), value);
+        }
+        card.insert("flavor".to_owned(), serde_json::json!("optional"));
+        let clean = Value::Object(card.clone());
+        assert!(SchemaReport::from_card(&clean).is_clean());
+
+        card.remove("rarity");
+        card.insert("cmc".to_owned(), serde_json::json!("6"));
+        card.insert("frameVersion".to_owned(), serde_json::json!("2015"));
+        let report = SchemaReport::from_card(&Value::Object(card));
+        assert_eq!(report.missing, vec!["rarity"]);
+        assert_eq!(report.mistyped, vec![("cmc".to_owned(), FieldKind::String)]);
+        assert_eq!(report.new, vec!["frameVersion"]);
+        assert!(!report.is_clean());
+    }
+
+    #[tokio::test]
+    async fn live_schema() {
+        let report = schema_check().await.unwrap();
+        assert!(report.is_clean(), "{:?}", report);
+    }
+}
@@ -0,0 +1,76 @@
+#![deny(missing_docs)]
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use mtg_api::MtgClient;
+
+use crate::{id_find_with, Card, MTGCardError};
+
+/// Wraps a [`MtgClient`], remembering the cards it has looked up by id
+///
+/// Enabled by the `cache` feature. Only successful lookups are cached.
+#[derive(Debug, Default)]
+pub struct CachedClient {
+    client: MtgClient,
+    cards: Mutex<HashMap<u64, Card>>,
+}
+
+impl CachedClient {
+    /// Cache the lookups of a default [`MtgClient`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cache the lookups of the given client
+    pub fn with_client(client: MtgClient) -> Self {
+        CachedClient {
+            client,
+            cards: Mutex::default(),
+        }
+    }
+
+    /// Like [`crate::id_find`] but a card already looked up is returned without a request
+    pub async fn id_find(&self, id: u64) -> Result<Card, MTGCardError> {
+        if let Some(card) = self.cards.lock().unwrap().get(&id) {
+            return Ok(card.clone());
+        }
+        let card = id_find_with(&self.client, id).await?.card;
+        self.cards.lock().unwrap().insert(id, card.clone());
+        Ok(card)
+    }
+
+    /// Number of cards currently cached
+    pub fn cached(&self) -> usize {
+        self.cards.lock().unwrap().len()
+    }
+
+    /// Forget every cached card, so the next lookups go to the network again
+    pub fn clear_cache(&self) {
+        self.cards.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mtg_api::mock;
+
+    #[tokio::test]
+    async fn cache_repeated_lookups() {
+        let card = || mock::json(r#"{"card":{"name":"Narset, Enlightened Master"}}"#);
+        let server = mock::serve(vec![card(), card()]).await;
+        let cached = CachedClient::with_client(MtgClient::new().with_base_url(&server.url));
+
+        let first = cached.id_find(386616).await.unwrap();
+        let second = cached.id_find(386616).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first.name, "Narset, Enlightened Master");
+        assert_eq!(server.paths().len(), 1);
+        assert_eq!(cached.cached(), 1);
+
+        cached.clear_cache();
+        assert_eq!(cached.cached(), 0);
+        cached.id_find(386616).await.unwrap();
+        assert_eq!(server.paths(), vec!["/cards/386616", "/cards/386616"]);
+    }
+}
@@ -0,0 +1,90 @@
+#![deny(missing_docs)]
+use crate::{Card, MTGCardError, MultiCards};
+
+/// Column headings of [`MultiCards::to_csv`]
+const HEADER: [&str; 5] = ["name", "mana_cost", "type", "rarity", "set_name"];
+
+impl From<csv::Error> for MTGCardError {
+    fn from(value: csv::Error) -> Self {
+        MTGCardError::WrappedCsv {
+            e: value.to_string(),
+        }
+    }
+}
+
+impl Card {
+    fn csv_row(&self) -> [&str; 5] {
+        [
+            &self.name,
+            &self.mana_cost,
+            &self.type_field,
+            &self.rarity,
+            &self.set_name,
+        ]
+    }
+}
+
+impl MultiCards {
+    /// Export the cards as CSV with a header row, then one row per card
+    ///
+    /// The columns are name, mana_cost, type, rarity and set_name. Fields holding commas,
+    /// quotes or line breaks are quoted by the [`csv`] writer as described in RFC 4180.
+    /// Errors with [`MTGCardError::WrappedCsv`] if the writer fails.
+    pub fn to_csv(&self) -> Result<String, MTGCardError> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+        writer.write_record(HEADER)?;
+        for card in &self.cards {
+            writer.write_record(card.csv_row())?;
+        }
+        let bytes = writer.into_inner().map_err(|e| MTGCardError::WrappedCsv {
+            e: e.error().to_string(),
+        })?;
+        String::from_utf8(bytes).map_err(|e| MTGCardError::WrappedCsv { e: e.to_string() })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote_fields() {
+        let json = r#"{"cards":[
+            {"name":"\"Ach! Hans, Run!\"","type":"Enchantment"},
+            {"name":"Lightning Bolt","type":"Flying\nHaste"}
+        ]}"#;
+        let csv = MultiCards::from_json(json).unwrap().to_csv().unwrap();
+        let rows: Vec<&str> = csv.split_terminator('\n').skip(1).collect();
+        assert_eq!(
+            rows,
+            vec![
+                "\"\"\"Ach! Hans, Run!\"\"\",,Enchantment,,",
+                "Lightning Bolt,,\"Flying",
+                "Haste\",,",
+            ]
+        );
+    }
+
+    #[test]
+    fn export_two_cards() {
+        let json = r#"{"cards":[
+            {"name":"Narset, Enlightened Master","manaCost":"{3}{U}{R}{W}","type":"Legendary Creature — Human Monk","rarity":"Mythic","setName":"Khans of Tarkir"},
+            {"name":"Lightning Bolt","manaCost":"{R}","type":"Instant","rarity":"Common","setName":"Magic 2010"}
+        ]}"#;
+        let cards = MultiCards::from_json(json).unwrap();
+        let csv = cards.to_csv().unwrap();
+        let rows: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            rows,
+            vec![
+                "name,mana_cost,type,rarity,set_name",
+                "\"Narset, Enlightened Master\",{3}{U}{R}{W},Legendary Creature — Human Monk,Mythic,Khans of Tarkir",
+                "Lightning Bolt,{R},Instant,Common,Magic 2010",
+            ]
+        );
+        assert_eq!(
+            MultiCards { cards: vec![] }.to_csv().unwrap(),
+            rows[0].to_owned() + "\n"
+        );
+    }
+}
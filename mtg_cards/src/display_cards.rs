@@ -1,43 +1,236 @@
 #![deny(missing_docs)]
+use std::collections::BTreeMap;
 use std::fmt;
 
+use colored::{Color as Ansi, Colorize};
+
+use crate::{Color, ManaSymbol};
+
+/// Options controlling how a card is rendered
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DisplayOptions {
+    /// Start every ability on its own line and collapse runs of blank lines
+    pub normalize_text: bool,
+    /// Leave out all ANSI styling, such as the colors of the rarity and mana symbols
+    ///
+    /// Styling is already left out when `NO_COLOR` is set or stdout is not a terminal.
+    pub plain: bool,
+    /// Keep the rarity and mana colors even when `NO_COLOR` is set or stdout is not a terminal
+    pub force_color: bool,
+    /// Number of columns each line is kept within, 50 by default
+    pub width: usize,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        DisplayOptions {
+            normalize_text: false,
+            plain: false,
+            force_color: false,
+            width: 50,
+        }
+    }
+}
+
 /// Divider out of a specified char
 pub fn divider(max: usize, ch: char, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     for _ in 0..max {
         write!(f, "{}", ch)?;
     }
-    write!(f, "\n")?;
+    writeln!(f)?;
     Ok(())
 }
 
 /// Two columns with spaces used as padding between.
+///
+/// The columns are always separated by at least one space. When they would not fit
+/// within the line limit the left column is cut short and ends in an ellipsis `…`.
+/// ANSI styling in the right column takes up no space.
 pub fn cols(left: &str, right: &str, max: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    let mut pad = "".to_string();
-    while pad.len() + left.len() + right.len() < max {
-        pad += " ";
-    }
-    write!(f, "{}{}{}\n", left, pad, right)?;
+    let right_len = visible_len(right);
+    let gap = usize::from(!left.is_empty() && !right.is_empty());
+    let room = max.saturating_sub(right_len + gap);
+    let left = match left.chars().count() > room {
+        true => truncate(left, room),
+        false => left.to_owned(),
+    };
+    let pad = max
+        .saturating_sub(left.chars().count() + right_len)
+        .max(gap);
+    writeln!(f, "{}{}{}", left, " ".repeat(pad), right)?;
     Ok(())
 }
 
+/// Number of chars in the text, not counting ANSI escape sequences such as `\x1b[31m`
+fn visible_len(text: &str) -> usize {
+    let mut len = 0;
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\x1b' => {
+                chars.by_ref().find(|c| c.is_ascii_alphabetic());
+            }
+            _ => len += 1,
+        }
+    }
+    len
+}
+
+/// Color of the rarity set symbol, `None` for rarities without one
+pub fn rarity_color(rarity: &str) -> Option<Ansi> {
+    match rarity {
+        "Mythic" | "Mythic Rare" => Some(Ansi::TrueColor {
+            r: 255,
+            g: 128,
+            b: 0,
+        }),
+        "Rare" => Some(Ansi::TrueColor {
+            r: 212,
+            g: 175,
+            b: 55,
+        }),
+        "Uncommon" => Some(Ansi::TrueColor {
+            r: 192,
+            g: 192,
+            b: 192,
+        }),
+        "Common" => Some(Ansi::White),
+        _ => None,
+    }
+}
+
+/// Terminal color of a mana symbol, `None` for symbols which are not a single color
+fn mana_color(symbol: &ManaSymbol) -> Option<Ansi> {
+    let color = match symbol {
+        ManaSymbol::Colored(c) | ManaSymbol::TwoHybrid(c) | ManaSymbol::Phyrexian(c) => c,
+        _ => return None,
+    };
+    Some(match color {
+        Color::White => Ansi::BrightWhite,
+        Color::Blue => Ansi::Blue,
+        Color::Black => Ansi::BrightBlack,
+        Color::Red => Ansi::Red,
+        Color::Green => Ansi::Green,
+    })
+}
+
+/// Text in a color, written out regardless of the terminal when `force` is set
+fn paint(text: &str, color: Ansi, force: bool) -> String {
+    match force {
+        true => format!("\x1b[{}m{}\x1b[0m", color.to_fg_str(), text),
+        false => text.color(color).to_string(),
+    }
+}
+
+/// The rarity colored as its set symbol would be
+pub fn styled_rarity(rarity: &str, force: bool) -> String {
+    match rarity_color(rarity) {
+        Some(color) => paint(rarity, color, force),
+        None => rarity.to_owned(),
+    }
+}
+
+/// The mana cost with each single colored symbol in its color, other text is kept as is
+pub fn styled_mana(cost: &str, force: bool) -> String {
+    let mut groups = cost.split('{');
+    let mut styled = groups.next().unwrap_or_default().to_owned();
+    for group in groups {
+        let Some((inner, rest)) = group.split_once('}') else {
+            styled += &format!("{{{}", group);
+            continue;
+        };
+        let raw = format!("{{{}}}", inner);
+        match mana_color(&ManaSymbol::parse(inner)) {
+            Some(color) => styled += &paint(&raw, color, force),
+            None => styled += &raw,
+        }
+        styled += rest;
+    }
+    styled
+}
+
+/// Cut text down to `max` chars with the last replaced by an ellipsis, always keeping it
+fn truncate(text: &str, max: usize) -> String {
+    let kept: String = text.chars().take(max.saturating_sub(1)).collect();
+    kept + "…"
+}
+
 /// Wrap block of text to a line limit.
 ///
-/// TODO: Wrap nicely around whole words
+/// Lines break between whole words, keeping any explicit newlines. A word longer than
+/// the limit is broken wherever it reaches the limit.
 pub fn wrap(body: &str, max: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    let mut count = 0;
-    for ch in body.chars() {
-        if ch == '\n' {
-            count = 0;
-            write!(f, "{}", ch)?;
-        } else if count % max == 0 && count != 0 {
-            write!(f, "\n{}", ch)?;
-            count += 1;
-        } else {
-            write!(f, "{}", ch)?;
-            count += 1;
+    let max = max.max(1);
+    for (i, paragraph) in body.split('\n').enumerate() {
+        if i != 0 {
+            writeln!(f)?;
+        }
+        let mut count = 0;
+        for word in paragraph.split_whitespace() {
+            let mut word_len = word.chars().count();
+            if count != 0 && count + 1 + word_len <= max {
+                write!(f, " ")?;
+                count += 1;
+            } else if count != 0 {
+                writeln!(f)?;
+                count = 0;
+            }
+
+            // Hard break any word which can't fit on a line of its own
+            let mut rest = word;
+            while word_len > max {
+                let (split, _) = rest.char_indices().nth(max).unwrap();
+                writeln!(f, "{}", &rest[..split])?;
+                rest = &rest[split..];
+                word_len -= max;
+            }
+            write!(f, "{}", rest)?;
+            count += word_len;
         }
     }
-    write!(f, "\n")?;
+    writeln!(f)?;
+    Ok(())
+}
+
+/// Tidy rules text so each ability sits on its own line.
+///
+/// Surrounding whitespace is trimmed from every line and runs of blank lines collapse into one.
+pub fn normalize_abilities(text: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() && lines.last().is_none_or(|l| l.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    while lines.last() == Some(&"") {
+        lines.pop();
+    }
+    lines.join("\n")
+}
+
+/// Horizontal bar chart with one row per key from 0 up to the largest key.
+///
+/// Bars are scaled so that the largest count still fits within the line limit.
+pub fn histogram(
+    rows: &BTreeMap<u32, usize>,
+    max: usize,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let (Some(&last), Some(&top)) = (rows.keys().max(), rows.values().max()) else {
+        return Ok(());
+    };
+    if top == 0 {
+        return Ok(());
+    }
+    let label_width = last.to_string().len();
+    let bar_max = max.saturating_sub(label_width + top.to_string().len() + 3);
+    for key in 0..=last {
+        let count = rows.get(&key).copied().unwrap_or(0);
+        let bar = "#".repeat((count * bar_max).div_ceil(top));
+        let label = format!("{:>width$} |{}", key, bar, width = label_width);
+        cols(&label, &count.to_string(), max, f)?;
+    }
     Ok(())
 }
 
@@ -92,11 +285,81 @@ mod tests {
 
         assert_eq!(&format!("{tester}")[..4], "l r\n");
         tester.line = 0;
-        assert_eq!(&format!("{tester}")[..3], "lr\n");
+        assert_eq!(&format!("{tester}")[..6], "… r\n");
         tester.line = 12;
         assert_eq!(&format!("{tester}")[..13], "l          r\n");
     }
 
+    #[test]
+    fn format_col_overflow() {
+        let mut tester = Foo {
+            left: "Asmoranomardicadaistinaculdacar, the Long Named".to_owned(),
+            right: "{1}{B}{R}".to_owned(),
+            body: "".to_owned(),
+            line: 30,
+            div: '.',
+        };
+
+        let first = format!("{tester}").lines().next().unwrap().to_owned();
+        assert_eq!(first.chars().count(), 30);
+        assert_eq!(first, "Asmoranomardicadais… {1}{B}{R}");
+
+        // Left fills the line exactly so only the ellipsis marks the cut
+        tester.right = "".to_owned();
+        tester.line = 10;
+        assert_eq!(&format!("{tester}")[..13], "Asmoranom…\n");
+
+        // Fits with a single space to spare
+        tester.left = "Narset".to_owned();
+        tester.right = "{3}".to_owned();
+        tester.line = 10;
+        assert_eq!(&format!("{tester}")[..11], "Narset {3}\n");
+    }
+
+    #[test]
+    fn format_col_styled() {
+        let tester = Foo {
+            left: "l".to_owned(),
+            right: "\x1b[31mr\x1b[0m".to_owned(),
+            body: "".to_owned(),
+            line: 5,
+            div: '.',
+        };
+        assert_eq!(&format!("{tester}")[..15], "l   \x1b[31mr\x1b[0m\n");
+    }
+
+    #[test]
+    fn rarity_colors() {
+        let orange = rarity_color("Mythic").unwrap();
+        assert_eq!(orange.to_fg_str(), "38;2;255;128;0");
+        assert_eq!("Mythic".color(orange).fgcolor(), Some(orange));
+        assert_eq!(rarity_color("Common"), Some(Ansi::White));
+        assert_eq!(rarity_color("Special"), None);
+        assert_eq!(styled_rarity("Special", true), "Special");
+    }
+
+    #[test]
+    fn render_rarity_color() {
+        let mythic = crate::Card::from_json(r#"{"name":"Narset","rarity":"Mythic"}"#).unwrap();
+        let options = DisplayOptions {
+            force_color: true,
+            ..Default::default()
+        };
+        let shown = mythic.display_with(options).to_string();
+        assert!(shown.contains("\x1b[38;2;255;128;0mMythic\x1b[0m"));
+        assert!(!mythic.display_plain().contains('\x1b'));
+    }
+
+    #[test]
+    fn mana_colors() {
+        let symbols = crate::ManaCost::parse("{3}{U}{W/U}{R/P}");
+        let colors: Vec<Option<Ansi>> = symbols.symbols().iter().map(mana_color).collect();
+        assert_eq!(colors, vec![None, Some(Ansi::Blue), None, Some(Ansi::Red)]);
+
+        // Text outside of symbols is kept as is
+        assert_eq!(styled_mana("mana {2}{", true), "mana {2}{");
+    }
+
     #[test]
     fn format_divider() {
         let mut tester = Foo {
@@ -115,6 +378,17 @@ mod tests {
         assert_eq!(&format!("{tester}")[..13], "     \n\naaaaa\n");
     }
 
+    #[test]
+    fn normalize_text() {
+        let text = "Flying  \n\n\n  Whenever this attacks, draw a card.\r\n\nProwess\n\n";
+        assert_eq!(
+            normalize_abilities(text),
+            "Flying\n\nWhenever this attacks, draw a card.\n\nProwess"
+        );
+        assert_eq!(normalize_abilities("Trample\nHaste"), "Trample\nHaste");
+        assert_eq!(normalize_abilities("\n\n"), "");
+    }
+
     #[test]
     fn format_wrap() {
         let mut tester = Foo {
@@ -125,11 +399,33 @@ mod tests {
             div: '.',
         };
 
-        assert_eq!(&format!("{tester}"), "     \nThis \nis a \ntest\n.....\n");
+        assert_eq!(&format!("{tester}"), "     \nThis\nis a\ntest\n.....\n");
         tester.line = 1;
         assert_eq!(&format!("{tester}")[..10], " \nT\nh\ni\ns\n");
         tester.line = 5;
         tester.body = "New\nline".to_owned();
         assert_eq!(&format!("{tester}"), "     \nNew\nline\n.....\n");
     }
+
+    #[test]
+    fn format_wrap_words() {
+        let mut tester = Foo {
+            left: "".to_owned(),
+            right: "".to_owned(),
+            body: "Flying, first strike, hexproof".to_owned(),
+            line: 16,
+            div: '.',
+        };
+
+        let wrapped = format!("{tester}");
+        let body: Vec<&str> = wrapped.lines().skip(1).take(2).collect();
+        assert_eq!(body, vec!["Flying, first", "strike, hexproof"]);
+
+        // Words longer than the line are broken at the limit
+        tester.body = "Indestructible trample".to_owned();
+        tester.line = 6;
+        let wrapped = format!("{tester}");
+        let body: Vec<&str> = wrapped.lines().skip(1).take(5).collect();
+        assert_eq!(body, vec!["Indest", "ructib", "le", "trampl", "e"]);
+    }
 }
@@ -1,8 +1,13 @@
 #![deny(missing_docs)]
 
+use std::collections::HashMap;
+use std::fmt;
 use std::num::ParseIntError;
 
-use reqwest::{header::ToStrError, Response};
+use reqwest::{
+    header::{HeaderMap, ToStrError},
+    Response,
+};
 use thiserror::Error;
 
 /// Errors generated while processing Headers from Requests to the MTG API
@@ -38,6 +43,7 @@ impl From<ParseIntError> for MTGHeaderError {
     }
 }
 
+/// Pagination and rate limit information returned in the headers of a response
 #[derive(Debug)]
 pub struct MTGHeader {
     link: String,
@@ -49,20 +55,88 @@ pub struct MTGHeader {
 }
 
 impl MTGHeader {
+    /// Read the header fields of a [`Response`] without consuming its body
+    ///
+    /// The `Link` header is left out when every result fits on one page, so it is read as
+    /// empty if missing.
     pub async fn from_response(res: &Response) -> Result<Self, MTGHeaderError> {
+        Self::from_headers(res.headers())
+    }
+
+    /// Read the header fields from headers already taken out of a response
+    ///
+    /// See [`mtg_api::split_response`] for getting them alongside the body.
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, MTGHeaderError> {
+        let link = match Self::get_field(headers, "Link") {
+            Err(MTGHeaderError::ItemMissing { .. }) => String::new(),
+            link => link?,
+        };
         Ok(MTGHeader {
-            link: Self::get_field(res, "Link").await?,
-            page_size: Self::get_field(res, "Page-Size").await?.parse()?,
-            count: Self::get_field(res, "Count").await?.parse()?,
-            total_count: Self::get_field(res, "Total-Count").await?.parse()?,
-            ratelimit_limit: Self::get_field(res, "Ratelimit-Limit").await?.parse()?,
-            ratelimit_remaining: Self::get_field(res, "Ratelimit-Remaining").await?.parse()?,
+            link,
+            page_size: Self::get_field(headers, "Page-Size")?.parse()?,
+            count: Self::get_field(headers, "Count")?.parse()?,
+            total_count: Self::get_field(headers, "Total-Count")?.parse()?,
+            ratelimit_limit: Self::get_field(headers, "Ratelimit-Limit")?.parse()?,
+            ratelimit_remaining: Self::get_field(headers, "Ratelimit-Remaining")?.parse()?,
         })
     }
 
-    async fn get_field(res: &Response, item: &str) -> Result<String, MTGHeaderError> {
-        Ok(res
-            .headers()
+    /// The raw `Link` header, see [`MTGHeader::links`] for it parsed
+    pub fn link(&self) -> &str {
+        &self.link
+    }
+
+    /// Number of cards the API puts on each page
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    /// Number of cards in this response
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Total number of cards matching the request, across every page
+    ///
+    /// ```
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let res = mtg_api::card_page("1").await.unwrap();
+    /// let header = mtg_cards::MTGHeader::from_response(&res).await.unwrap();
+    /// assert!(header.total_count() > 0);
+    /// # }
+    /// ```
+    pub fn total_count(&self) -> usize {
+        self.total_count
+    }
+
+    /// Number of requests allowed in each rate limit window
+    pub fn ratelimit_limit(&self) -> usize {
+        self.ratelimit_limit
+    }
+
+    /// Number of requests left before the rate limit is hit
+    pub fn ratelimit_remaining(&self) -> usize {
+        self.ratelimit_remaining
+    }
+
+    /// Pagination links from the `Link` header keyed by their rel, e.g. `"next"` to its URL
+    ///
+    /// Only the rels the API sent are present, the first page has no `"prev"`.
+    pub fn links(&self) -> HashMap<String, String> {
+        self.link
+            .split('<')
+            .filter_map(|entry| {
+                let (url, params) = entry.split_once('>')?;
+                let (_, rel) = params.split_once("rel=\"")?;
+                let (rel, _) = rel.split_once('"')?;
+                Some((rel.to_owned(), url.to_owned()))
+            })
+            .collect()
+    }
+
+    fn get_field(headers: &HeaderMap, item: &str) -> Result<String, MTGHeaderError> {
+        Ok(headers
             .get(item)
             .ok_or(MTGHeaderError::ItemMissing { n: item.to_owned() })?
             .to_str()?
@@ -70,6 +144,24 @@ impl MTGHeader {
     }
 }
 
+impl fmt::Display for MTGHeader {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let next = match self.link.contains("rel=\"next\"") {
+            true => "yes",
+            false => "no",
+        };
+        writeln!(f, "Total count: {}", self.total_count)?;
+        writeln!(f, "Page size: {}", self.page_size)?;
+        writeln!(f, "Count: {}", self.count)?;
+        writeln!(
+            f,
+            "Rate limit: {}/{} remaining",
+            self.ratelimit_remaining, self.ratelimit_limit
+        )?;
+        writeln!(f, "Next page: {}", next)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -89,6 +181,68 @@ mod tests {
         assert!(header.ratelimit_remaining > 0);
     }
 
+    #[tokio::test]
+    async fn construct_sized_header() {
+        let res = mtg_api::card_page_sized("1", 10).await.unwrap();
+        let header = MTGHeader::from_response(&res).await.unwrap();
+        assert_eq!(header.page_size, 10);
+        assert_eq!(header.count, 10);
+
+        let res = mtg_api::card_page_sized("1", 250).await.unwrap();
+        let header = MTGHeader::from_response(&res).await.unwrap();
+        assert_eq!(header.page_size, 100);
+    }
+
+    #[tokio::test]
+    async fn first_page_links() {
+        let res = mtg_api::card_page("1").await.unwrap();
+        let links = MTGHeader::from_response(&res).await.unwrap().links();
+        assert!(links.contains_key("next"));
+        assert!(links.contains_key("last"));
+        assert!(!links.contains_key("prev"));
+    }
+
+    #[test]
+    fn parse_links() {
+        let mut header = MTGHeader {
+            link: "<https://api.magicthegathering.io/v1/cards?colors=red,white&page=3>; rel=\"last\", <https://api.magicthegathering.io/v1/cards?colors=red,white&page=2>; rel=\"next\"".to_owned(),
+            page_size: 100,
+            count: 100,
+            total_count: 300,
+            ratelimit_limit: 1000,
+            ratelimit_remaining: 998,
+        };
+        let links = header.links();
+        assert_eq!(links.len(), 2);
+        assert_eq!(
+            links["next"],
+            "https://api.magicthegathering.io/v1/cards?colors=red,white&page=2"
+        );
+        assert!(links["last"].ends_with("page=3"));
+
+        header.link = "".to_owned();
+        assert!(header.links().is_empty());
+    }
+
+    #[test]
+    fn display_header() {
+        let mut header = MTGHeader {
+            link: "<https://api.magicthegathering.io/v1/cards?page=2>; rel=\"next\"".to_owned(),
+            page_size: 100,
+            count: 100,
+            total_count: 93643,
+            ratelimit_limit: 1000,
+            ratelimit_remaining: 998,
+        };
+        assert_eq!(
+            header.to_string(),
+            "Total count: 93643\nPage size: 100\nCount: 100\nRate limit: 998/1000 remaining\nNext page: yes\n"
+        );
+
+        header.link = "".to_owned();
+        assert!(header.to_string().ends_with("Next page: no\n"));
+    }
+
     #[test]
     fn conversion_error_parse_int() {
         let err_res = " 12 ".parse::<usize>();
@@ -97,9 +251,6 @@ mod tests {
         let err = err_res.unwrap_err();
         let header_err: MTGHeaderError = err.clone().into();
 
-        assert_eq!(
-            header_err.to_string(),
-            format!("Conversion Error: {}", err.to_string())
-        );
+        assert_eq!(header_err.to_string(), format!("Conversion Error: {}", err));
     }
 }
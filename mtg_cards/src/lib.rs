@@ -2,14 +2,51 @@
 
 #![deny(missing_docs)]
 use reqwest::Response;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
 
 use colored::Colorize;
-use display_cards::{cols, divider, wrap};
-use serde::{Deserialize, Serialize};
+use display_cards::{
+    cols, divider, histogram, normalize_abilities, styled_mana, styled_rarity, wrap,
+};
+use futures_util::{stream, StreamExt, TryStreamExt};
+use mtg_api::{CardQuery, MtgClient};
+use serde::{Deserialize, Deserializer, Serialize};
 use thiserror::Error;
+#[cfg(feature = "cache")]
+mod cache_cards;
+#[cfg(feature = "csv")]
+mod csv_cards;
 mod display_cards;
 mod header_cards;
+mod mana;
+mod paginator_cards;
+mod set_cards;
+#[cfg(feature = "stream")]
+mod stream_cards;
+mod vocab_cards;
+
+#[cfg(feature = "cache")]
+pub use cache_cards::CachedClient;
+pub use display_cards::DisplayOptions;
+pub use header_cards::{MTGHeader, MTGHeaderError};
+pub use mana::{Color, ManaCost, ManaSymbol};
+pub use paginator_cards::CardPaginator;
+pub use set_cards::{sets_find, sets_find_with, warm_sets_cache, MultiSets, Set, SetsCache};
+#[cfg(feature = "stream")]
+pub use stream_cards::{all_cards, all_cards_indexed, QueryStream};
+pub use vocab_cards::{formats_list, subtypes_list, supertypes_list, types_list};
+
+/// Most converted mana costs [`cmc_range_find`] searches for in one call
+pub const MAX_CMC_RANGE: u32 = 8;
+
+/// Number of requests [`pages_find`], [`ids_find`], [`open_packs`] and [`cards_in_sets`] send at once
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Times [`distinct_card_count`] and other crawls retry a rate limited request before giving up
+pub const CRAWL_RETRIES: u32 = 5;
 
 /// Errors generated while making MTG Cards
 #[derive(Clone, Debug, Error)]
@@ -26,9 +63,76 @@ pub enum MTGCardError {
         /// The Wrapped Error
         e: String,
     },
+    #[error("Wrapped Header Error: {e}")]
+    /// Contains Errors from reading the headers of a response
+    WrappedHeader {
+        /// The Wrapped Error
+        e: MTGHeaderError,
+    },
+    #[error("Wrapped CSV Error: {e}")]
+    /// Contains Errors from writing cards as CSV, see `MultiCards::to_csv`
+    WrappedCsv {
+        /// The Wrapped Error
+        e: String,
+    },
     #[error("No Card Found")]
     /// Error for when no card can be found by given identifier
     NoCardError {},
+    #[error("Card Has No Image")]
+    /// Error for when a card without an `imageUrl`, such as some promos, has its image requested
+    NoImageError {},
+    #[error("Invalid Date: {date}")]
+    /// Error for when a date is not written as `YYYY-MM-DD`
+    InvalidDate {
+        /// The date as given
+        date: String,
+    },
+    #[error("Unknown Set Codes: {}", codes.join(", "))]
+    /// Error for when a search names sets that the API does not list, so nothing is fetched
+    UnknownSets {
+        /// Each code that matched no set
+        codes: Vec<String>,
+    },
+}
+
+impl MTGCardError {
+    /// The HTTP status code behind the error, if the API responded with one
+    ///
+    /// A lookup answered with `404 Not Found`, such as [`id_find`] or [`booster_find`], is
+    /// a [`MTGCardError::WrappedAPI`] holding [`mtg_api::APIError::NotFound`], giving 404.
+    pub fn status_code(&self) -> Option<reqwest::StatusCode> {
+        match self {
+            MTGCardError::WrappedAPI { e } => e.status_code(),
+            _ => None,
+        }
+    }
+
+    /// Describe the error for a person, with a hint on how to resolve it
+    pub fn user_message(&self) -> String {
+        match self {
+            MTGCardError::WrappedAPI { e } => e.user_message(),
+            MTGCardError::WrappedSerde { e } => {
+                format!("Could not read the card data sent by the MTG API ({e})")
+            }
+            MTGCardError::WrappedHeader { e } => {
+                format!("The MTG API sent unexpected pagination headers ({e})")
+            }
+            MTGCardError::WrappedCsv { e } => format!("Could not write the cards as CSV ({e})"),
+            MTGCardError::NoCardError {} => {
+                "No card matched; check spelling or try a partial search".to_owned()
+            }
+            MTGCardError::NoImageError {} => {
+                "The MTG API has no image of this card; try another printing".to_owned()
+            }
+            MTGCardError::InvalidDate { date } => {
+                format!("{date} is not a date; write it as YYYY-MM-DD, e.g. 2024-02-09")
+            }
+            MTGCardError::UnknownSets { codes } => format!(
+                "No set has the code {}; check the codes listed by sets_find",
+                codes.join(", ")
+            ),
+        }
+    }
 }
 
 impl From<mtg_api::APIError> for MTGCardError {
@@ -37,6 +141,12 @@ impl From<mtg_api::APIError> for MTGCardError {
     }
 }
 
+impl From<MTGHeaderError> for MTGCardError {
+    fn from(value: MTGHeaderError) -> Self {
+        MTGCardError::WrappedHeader { e: value }
+    }
+}
+
 impl From<serde_json::Error> for MTGCardError {
     fn from(value: serde_json::Error) -> Self {
         MTGCardError::WrappedSerde {
@@ -58,25 +168,436 @@ pub struct Card {
     set_name: String,
     text: String,
     flavor: String,
+    names: Vec<String>,
+    layout: String,
+    cmc: f32,
+    power: String,
+    toughness: String,
+    loyalty: String,
+    colors: Vec<String>,
+    color_identity: Vec<String>,
+    image_url: String,
+    #[serde(deserialize_with = "number_or_string")]
+    multiverseid: Option<u64>,
+    legalities: Vec<Legality>,
+    artist: String,
+    supertypes: Vec<String>,
+    types: Vec<String>,
+    subtypes: Vec<String>,
+    printings: Vec<String>,
+    rulings: Vec<Ruling>,
+    foreign_names: Vec<ForeignName>,
+    set: String,
+    number: String,
+    release_date: String,
+    id: String,
+    watermark: String,
+    original_text: String,
+    original_type: String,
+    reserved: bool,
+    border: String,
+    timeshifted: bool,
+    #[serde(deserialize_with = "number_or_string")]
+    hand: Option<i32>,
+    #[serde(deserialize_with = "number_or_string")]
+    life: Option<i32>,
+    starter: bool,
+}
+
+/// Whether a card may be played in a format
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Legality {
+    /// The format, e.g. `Commander`
+    pub format: String,
+    /// The legality in the format, e.g. `Legal`, `Banned` or `Restricted`
+    pub legality: String,
+}
+
+/// A ruling clarifying how a card works
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+pub struct Ruling {
+    /// When the ruling was made, formatted as `YYYY-MM-DD`
+    pub date: String,
+    /// The text of the ruling
+    pub text: String,
+}
+
+/// A card as printed in another language
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct ForeignName {
+    /// The translated name
+    pub name: String,
+    /// The translated rules text
+    pub text: String,
+    /// The translated flavor text
+    pub flavor: String,
+    /// The language, e.g. `German`
+    pub language: String,
+    /// Id of the translated printing on Gatherer
+    #[serde(deserialize_with = "number_or_string")]
+    pub multiverseid: Option<u64>,
+    /// Address of the translated printing's image on Gatherer
+    pub image_url: String,
+}
+
+/// Read an optional number which the API may send as a string, such as `"386616"` or `"+1"`
+fn number_or_string<'de, D, T>(d: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de> + FromStr,
+    T::Err: fmt::Display,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Raw<T> {
+        Number(T),
+        Text(String),
+    }
+    match Option::<Raw<T>>::deserialize(d)? {
+        None => Ok(None),
+        Some(Raw::Number(n)) => Ok(Some(n)),
+        Some(Raw::Text(t)) if t.trim().is_empty() => Ok(None),
+        Some(Raw::Text(t)) => t.trim().parse().map(Some).map_err(serde::de::Error::custom),
+    }
+}
+
+impl Card {
+    /// The converted mana cost, fractional for some un-set cards
+    pub fn cmc(&self) -> f32 {
+        self.cmc
+    }
+
+    /// Power of a creature as printed, e.g. `3` or `1+*`, empty for non-creatures
+    pub fn power(&self) -> &str {
+        &self.power
+    }
+
+    /// Toughness of a creature as printed, e.g. `2` or `*`, empty for non-creatures
+    pub fn toughness(&self) -> &str {
+        &self.toughness
+    }
+
+    /// Starting loyalty of a planeswalker, empty for other cards
+    pub fn loyalty(&self) -> &str {
+        &self.loyalty
+    }
+
+    /// Rules text of the card, empty for vanilla creatures and basic lands
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Rules text as originally printed, before any errata to the Oracle wording
+    pub fn original_text(&self) -> &str {
+        &self.original_text
+    }
+
+    /// Type line as originally printed, e.g. `Summon Goblin` on older cards
+    pub fn original_type(&self) -> &str {
+        &self.original_type
+    }
+
+    /// Flavor text of the card, empty if it has none
+    pub fn flavor(&self) -> &str {
+        &self.flavor
+    }
+
+    /// How the card is laid out, e.g. `normal`, `split`, `flip` or `transform`
+    pub fn layout(&self) -> &str {
+        &self.layout
+    }
+
+    /// Whether the card is one face of a split, flip, double-faced or other multi-part card
+    pub fn is_multifaced(&self) -> bool {
+        self.names.len() > 1 && self.layout != "normal"
+    }
+
+    /// Names of every face of a split, flip or double-faced card, empty for other cards
+    ///
+    /// For "Fire // Ice" both halves list `["Fire", "Ice"]`.
+    pub fn faces(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Colors of the card, empty for colorless cards
+    pub fn colors(&self) -> &[String] {
+        &self.colors
+    }
+
+    /// Colors of every mana symbol on the card, including its rules text
+    pub fn color_identity(&self) -> &[String] {
+        &self.color_identity
+    }
+
+    /// Id of the card on Gatherer, absent for some promos
+    pub fn multiverseid(&self) -> Option<u64> {
+        self.multiverseid
+    }
+
+    /// Supertypes of the card, e.g. `["Legendary"]`
+    pub fn supertypes(&self) -> &[String] {
+        &self.supertypes
+    }
+
+    /// Card types of the card, e.g. `["Creature"]`
+    pub fn types(&self) -> &[String] {
+        &self.types
+    }
+
+    /// Subtypes of the card, e.g. `["Human", "Monk"]`
+    pub fn subtypes(&self) -> &[String] {
+        &self.subtypes
+    }
+
+    /// Id the API gives this printing, a stable hash useful as a cache key
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Code of the set this printing is from, e.g. `KTK`
+    pub fn set(&self) -> &str {
+        &self.set
+    }
+
+    /// Collector number within the set, not always numeric, e.g. `12a`
+    pub fn number(&self) -> &str {
+        &self.number
+    }
+
+    /// When this printing was released, formatted as `YYYY-MM-DD`, empty if unknown
+    pub fn release_date(&self) -> &str {
+        &self.release_date
+    }
+
+    /// Codes of every set the card has been printed in, e.g. `["KTK", "C15"]`
+    pub fn printings(&self) -> &[String] {
+        &self.printings
+    }
+
+    /// Rulings on the card, oldest first
+    pub fn rulings(&self) -> &[Ruling] {
+        &self.rulings
+    }
+
+    /// The card as printed in each other language
+    pub fn foreign_names(&self) -> &[ForeignName] {
+        &self.foreign_names
+    }
+
+    /// Name of the card in the given language, e.g. `"German"`, ignoring case
+    pub fn name_in_language(&self, lang: &str) -> Option<&str> {
+        self.foreign_names
+            .iter()
+            .find(|f| f.language.eq_ignore_ascii_case(lang))
+            .map(|f| f.name.as_str())
+    }
+
+    /// Watermark printed in the text box, such as a guild symbol, empty for most cards
+    pub fn watermark(&self) -> &str {
+        &self.watermark
+    }
+
+    /// Whether the card is on the Reserved List, never to be reprinted
+    pub fn is_reserved(&self) -> bool {
+        self.reserved
+    }
+
+    /// Color of the border, e.g. `"black"`, `"white"` or `"silver"`
+    ///
+    /// The API may leave this out when the card has the usual border of its set.
+    pub fn border(&self) -> &str {
+        &self.border
+    }
+
+    /// Whether the card is a timeshifted printing, such as those of Time Spiral
+    pub fn is_timeshifted(&self) -> bool {
+        self.timeshifted
+    }
+
+    /// Whether the card was only printed in starter products, such as Portal decks
+    pub fn is_starter(&self) -> bool {
+        self.starter
+    }
+
+    /// Change to the starting hand size given by a Vanguard card, `None` for other cards
+    pub fn hand(&self) -> Option<i32> {
+        self.hand
+    }
+
+    /// Change to the starting life total given by a Vanguard card, `None` for other cards
+    pub fn life(&self) -> Option<i32> {
+        self.life
+    }
+
+    /// Name of the artist who illustrated the card
+    pub fn artist(&self) -> &str {
+        &self.artist
+    }
+
+    /// Whether the card is `Legal` or `Restricted` in the format, compared case-insensitively
+    pub fn playable_in(&self, format: &str) -> bool {
+        self.legalities.iter().any(|l| {
+            l.format.eq_ignore_ascii_case(format)
+                && (l.legality == "Legal" || l.legality == "Restricted")
+        })
+    }
+
+    /// Legality of the card in each format the API knows it to be played in
+    pub fn legalities(&self) -> &[Legality] {
+        &self.legalities
+    }
+
+    /// Address of the card's image on Gatherer, empty if the API has none
+    pub fn image_url(&self) -> &str {
+        &self.image_url
+    }
+
+    /// Download the image of the card
+    ///
+    /// Errors with [`MTGCardError::NoImageError`] if the card has no image.
+    pub async fn download_image(&self) -> Result<Vec<u8>, MTGCardError> {
+        if self.image_url.is_empty() {
+            return Err(MTGCardError::NoImageError {});
+        }
+        Ok(mtg_api::download(&self.image_url).await?)
+    }
+
+    /// Parse a single card object from JSON text, e.g. a cached payload
+    pub fn from_json(json: &str) -> Result<Self, MTGCardError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Parse a single card object, e.g. one taken out of a larger JSON document
+    pub fn from_json_value(v: &serde_json::Value) -> Result<Self, MTGCardError> {
+        Ok(Card::deserialize(v)?)
+    }
+
+    /// The mana cost rendered for terminals, e.g. `③🔵🔴⚪` for `{3}{U}{R}{W}`
+    ///
+    /// See [`ManaSymbol::pretty`] for how each symbol is shown.
+    pub fn pretty_mana(&self) -> String {
+        ManaCost::parse(&self.mana_cost).pretty()
+    }
+
+    /// Whether the mana value of the mana cost agrees with the `cmc` reported by the API
+    ///
+    /// `{X}` and other variable symbols count as 0, matching how the API reports `cmc`.
+    pub fn mana_cost_matches_cmc(&self) -> bool {
+        (ManaCost::parse(&self.mana_cost).mana_value() - self.cmc).abs() < f32::EPSILON
+    }
+
+    /// A numeric stand-in for power so creatures can be sorted by it
+    ///
+    /// A fixed power such as `3` is its value and a purely variable power such as `*`
+    /// is `1000.0`, sorting after any real creature. Forms like `1+*` are the fixed part
+    /// plus `0.5`, so they follow creatures of the base power. Cards without a power are
+    /// [`f32::MIN`].
+    pub fn power_sort_key(&self) -> f32 {
+        let power = self.power.trim();
+        if power.is_empty() {
+            return f32::MIN;
+        }
+        if let Ok(n) = power.parse() {
+            return n;
+        }
+        let fixed: Vec<f32> = power.split('+').filter_map(|p| p.parse().ok()).collect();
+        match fixed.is_empty() {
+            true => 1000.0,
+            false => fixed.iter().sum::<f32>() + 0.5,
+        }
+    }
+
+    /// A single decklist line such as `4 Lightning Bolt`
+    ///
+    /// A quantity of 0 produces an empty string, as the card has no place in the list.
+    pub fn to_deck_line(&self, quantity: u32) -> String {
+        match quantity {
+            0 => String::new(),
+            n => format!("{} {}", n, self.name),
+        }
+    }
+
+    /// Render the card without any ANSI styling, e.g. for writing to a file
+    pub fn display_plain(&self) -> String {
+        let options = DisplayOptions {
+            plain: true,
+            ..Default::default()
+        };
+        self.display_with(options).to_string()
+    }
+
+    /// Render the card with every line kept within `width` columns
+    pub fn display_with_width(&self, width: usize) -> CardDisplay<'_> {
+        let options = DisplayOptions {
+            width,
+            ..Default::default()
+        };
+        self.display_with(options)
+    }
+
+    /// Render the card using the given [`DisplayOptions`]
+    pub fn display_with(&self, options: DisplayOptions) -> CardDisplay<'_> {
+        CardDisplay {
+            card: self,
+            options,
+        }
+    }
 }
 
 impl fmt::Display for Card {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let maxl = 50;
+        self.display_with(DisplayOptions::default()).fmt(f)
+    }
+}
+
+/// A [`Card`] paired with the [`DisplayOptions`] to render it with
+pub struct CardDisplay<'a> {
+    card: &'a Card,
+    options: DisplayOptions,
+}
+
+impl fmt::Display for CardDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let card = self.card;
+        let maxl = self.options.width;
         divider(maxl, '*', f)?;
 
         // Name and Manacost
-        cols(&self.name, &self.mana_cost, maxl, f)?;
+        match self.options.plain {
+            true => cols(&card.name, &card.mana_cost, maxl, f)?,
+            false => cols(
+                &card.name,
+                &styled_mana(&card.mana_cost, self.options.force_color),
+                maxl,
+                f,
+            )?,
+        }
         divider(maxl, '-', f)?;
 
         // Types and rarity
-        cols(&self.type_field, &self.rarity, maxl, f)?;
+        match self.options.plain {
+            true => cols(&card.type_field, &card.rarity, maxl, f)?,
+            false => cols(
+                &card.type_field,
+                &styled_rarity(&card.rarity, self.options.force_color),
+                maxl,
+                f,
+            )?,
+        }
         divider(maxl, '-', f)?;
 
         // Text and Flavour
-        wrap(&self.text, maxl, f)?;
-        wrap(&self.flavor.italic(), maxl, f)?;
-        cols(&"", &self.set_name, maxl, f)?;
+        match self.options.normalize_text {
+            true => wrap(&normalize_abilities(&card.text), maxl, f)?,
+            false => wrap(&card.text, maxl, f)?,
+        }
+        match self.options.plain {
+            true => wrap(&card.flavor, maxl, f)?,
+            false => wrap(&card.flavor.italic(), maxl, f)?,
+        }
+        cols("", &card.set_name, maxl, f)?;
         divider(maxl, '*', f)?;
         Ok(())
     }
@@ -89,17 +610,192 @@ pub struct MultiCards {
     pub cards: Vec<Card>,
 }
 
+/// The cards stacked one after another, an empty list displays as nothing
+impl fmt::Display for MultiCards {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for card in &self.cards {
+            card.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// How entries without a name, such as some tokens and emblems, are handled in [`MultiCards`]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum NamelessCards {
+    /// Keep them as cards with an empty name
+    #[default]
+    Keep,
+    /// Leave them out of the results
+    Skip,
+}
+
 impl MultiCards {
     /// Attempt to convert a [`Response`] into [`MultiCards`]
     pub async fn from_response(res: Response) -> Result<Self, MTGCardError> {
+        Self::from_response_with(res, NamelessCards::default()).await
+    }
+
+    /// Attempt to convert a [`Response`] into [`MultiCards`], handling nameless entries as given
+    ///
+    /// Errors with [`MTGCardError::NoCardError`] if skipping nameless entries leaves no cards.
+    pub async fn from_response_with(
+        res: Response,
+        nameless: NamelessCards,
+    ) -> Result<Self, MTGCardError> {
         match mtg_api::check_for_empty(res).await? {
-            Some(json) => {
-                let res: MultiCards = serde_json::from_str(&json)?;
-                Ok(res)
-            }
+            Some(json) => Self::parse(&json, nameless),
+            None => Err(MTGCardError::NoCardError {}),
+        }
+    }
+
+    /// Like [`MultiCards::from_response`] but also returns the [`MTGHeader`] of the response
+    pub async fn from_response_with_header(
+        res: Response,
+    ) -> Result<(Self, MTGHeader), MTGCardError> {
+        let (headers, body) = mtg_api::split_response(res).await?;
+        let header = MTGHeader::from_headers(&headers)?;
+        match body {
+            Some(json) => Ok((Self::parse(&json, NamelessCards::default())?, header)),
             None => Err(MTGCardError::NoCardError {}),
         }
     }
+
+    /// Parse a `{"cards":[...]}` document from JSON text, e.g. a cached payload
+    pub fn from_json(json: &str) -> Result<Self, MTGCardError> {
+        Self::parse(json, NamelessCards::default())
+    }
+
+    /// Serialise to compact JSON in the same `{"cards":[...]}` shape the API returns
+    pub fn to_json(&self) -> Result<String, MTGCardError> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Serialise to indented JSON, e.g. for caching search results to disk
+    pub fn to_pretty_json(&self) -> Result<String, MTGCardError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Like [`MultiCards::from_response`] but an empty response gives no cards instead of an error
+    pub async fn from_response_allow_empty(res: Response) -> Result<Self, MTGCardError> {
+        match mtg_api::check_for_empty(res).await? {
+            Some(json) => Self::parse(&json, NamelessCards::default()),
+            None => Ok(MultiCards { cards: vec![] }),
+        }
+    }
+
+    fn parse(json: &str, nameless: NamelessCards) -> Result<Self, MTGCardError> {
+        let mut res: MultiCards = serde_json::from_str(json)?;
+        if nameless == NamelessCards::Skip {
+            res.cards.retain(|c| !c.name.trim().is_empty());
+            if res.cards.is_empty() {
+                return Err(MTGCardError::NoCardError {});
+            }
+        }
+        Ok(res)
+    }
+
+    /// Number of non-land cards at each mana value, fractional values are rounded down
+    pub fn mana_curve(&self) -> BTreeMap<u32, usize> {
+        let mut curve = BTreeMap::new();
+        for card in self.cards.iter().filter(|c| !c.type_field.contains("Land")) {
+            *curve.entry(card.cmc as u32).or_insert(0) += 1;
+        }
+        curve
+    }
+
+    /// Join several collections into one, keeping every card in order
+    pub fn merge_all(parts: impl IntoIterator<Item = MultiCards>) -> Self {
+        MultiCards {
+            cards: parts.into_iter().flat_map(|part| part.cards).collect(),
+        }
+    }
+
+    /// Keep only the first printing of each card name
+    pub fn dedup_names(&mut self) {
+        let mut seen = HashSet::new();
+        self.cards.retain(|c| seen.insert(c.name.clone()));
+    }
+
+    /// Group the halves of split, adventure and other multi-part cards together
+    ///
+    /// Entries of a multi-part card printed in the same set share a group, ordered as the
+    /// card lists its `names`. Every other card is in a group on its own. Groups keep the
+    /// order in which their first entry appeared.
+    pub fn group_multipart(&self) -> Vec<Vec<Card>> {
+        let mut groups: Vec<Vec<Card>> = Vec::new();
+        for card in &self.cards {
+            let multipart = card.is_multifaced();
+            let existing = groups
+                .iter_mut()
+                .find(|g| multipart && g[0].names == card.names && g[0].set_name == card.set_name);
+            match existing {
+                Some(group) => group.push(card.clone()),
+                None => groups.push(vec![card.clone()]),
+            }
+        }
+        for group in &mut groups {
+            group.sort_by_key(|c| c.names.iter().position(|n| *n == c.name));
+        }
+        groups
+    }
+
+    /// Gather the [`SearchStats`] of the cards in a single pass
+    pub fn stats(&self) -> SearchStats {
+        let mut stats = SearchStats {
+            total: self.cards.len(),
+            ..Default::default()
+        };
+        let mut names = HashSet::new();
+        let (mut spells, mut mana_value) = (0, 0.0);
+        for card in &self.cards {
+            names.insert(card.name.as_str());
+            for color in ManaCost::parse(&card.mana_cost).colors() {
+                *stats.colors.entry(color).or_insert(0) += 1;
+            }
+            *stats.rarities.entry(card.rarity.clone()).or_insert(0) += 1;
+            if !card.type_field.contains("Land") {
+                spells += 1;
+                mana_value += card.cmc;
+            }
+        }
+        stats.distinct_names = names.len();
+        if spells > 0 {
+            stats.average_mana_value = mana_value / spells as f32;
+        }
+        stats
+    }
+
+    /// ASCII bar chart of [`MultiCards::mana_curve`], one row per mana value
+    ///
+    /// Returns an empty string when there are no non-land cards.
+    pub fn mana_curve_chart(&self) -> String {
+        ManaCurveChart(self.mana_curve()).to_string()
+    }
+}
+
+/// Key statistics of a collection of cards, see [`MultiCards::stats`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchStats {
+    /// Number of cards, counting every printing
+    pub total: usize,
+    /// Number of distinct card names
+    pub distinct_names: usize,
+    /// Number of cards whose mana cost includes each color
+    pub colors: BTreeMap<Color, usize>,
+    /// Number of cards of each rarity
+    pub rarities: BTreeMap<String, usize>,
+    /// Mean mana value of the non-land cards, 0 if there are none
+    pub average_mana_value: f32,
+}
+
+/// Renders a mana curve using [`histogram`]
+struct ManaCurveChart(BTreeMap<u32, usize>);
+
+impl fmt::Display for ManaCurveChart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        histogram(&self.0, 50, f)
+    }
 }
 
 /// Wrapper struct for individual card response
@@ -123,59 +819,847 @@ impl IndiCard {
 }
 
 /// Takes a card id to find and returns it deserialised into [`IndiCard`]
+///
+/// Errors with [`mtg_api::APIError::NotFound`], wrapped in [`MTGCardError::WrappedAPI`],
+/// if there is no card with that id.
 pub async fn id_find(id: u64) -> Result<IndiCard, MTGCardError> {
     let id_s = id.to_string();
-    Ok(IndiCard::from_response(mtg_api::card_id_info(&id_s).await?).await?)
+    IndiCard::from_response(mtg_api::card_id_info(&id_s).await?).await
 }
 
-/// Takes a card name to find and returns them deserialised into [`MultiCards`]
-pub async fn name_find(name: &str) -> Result<MultiCards, MTGCardError> {
-    Ok(MultiCards::from_response(mtg_api::card_exact_name_info(name).await?).await?)
+/// Like [`id_find`] but sends the request through the given client
+pub async fn id_find_with(client: &MtgClient, id: u64) -> Result<IndiCard, MTGCardError> {
+    let id_s = id.to_string();
+    IndiCard::from_response(client.card_id_info(&id_s).await?).await
 }
 
-/// Takes a page number to fetch cards from and returns them deserialised into [`MultiCards`]
-pub async fn page_find(number: u64) -> Result<MultiCards, MTGCardError> {
-    let index = number.to_string();
-    Ok(MultiCards::from_response(mtg_api::card_page(&index).await?).await?)
+/// Takes card ids to find and returns their cards, in the same order as `ids`
+///
+/// Up to [`DEFAULT_CONCURRENCY`] cards are requested at once. If any lookup fails the
+/// whole fetch stops with its error.
+pub async fn ids_find(ids: &[u64]) -> Result<Vec<Card>, MTGCardError> {
+    stream::iter(ids)
+        .map(|&id| async move { Ok::<_, MTGCardError>(id_find(id).await?.card) })
+        .buffered(DEFAULT_CONCURRENCY)
+        .try_collect()
+        .await
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use serde::de::Error;
+/// Takes a card name to find and returns them deserialised into [`MultiCards`]
+pub async fn name_find(name: &str) -> Result<MultiCards, MTGCardError> {
+    MultiCards::from_response(mtg_api::card_exact_name_info(name).await?).await
+}
 
-    #[tokio::test]
-    async fn find_card_id() {
-        // Get a known card Narset, Enlightened Master
-        let a = id_find(386616).await;
-        assert!(a.is_ok());
+/// Like [`name_find`] but with one entry per card, keeping its latest printing
+///
+/// The API does not promise any order of printings, so they are sorted by the release
+/// date of their set from [`sets_find`], taking a second request unless
+/// [`warm_sets_cache`] has run. A printing whose set has no known date is kept only if
+/// the card has no dated printing.
+pub async fn name_find_unique(name: &str) -> Result<MultiCards, MTGCardError> {
+    let found = name_find(name).await?;
+    Ok(unique_by_date(found.cards, &sets_find().await?.sets))
+}
 
-        // Check Individual fields of card struct
-        let c = &a.unwrap().card;
-        assert_eq!(c.name, "Narset, Enlightened Master");
-        assert_eq!(c.mana_cost, "{3}{U}{R}{W}");
-        assert_eq!(c.type_field, "Legendary Creature — Human Monk");
-        assert_eq!(c.rarity, "Mythic");
-        assert_eq!(c.set_name, "Khans of Tarkir");
-        assert_eq!(&c.text[..22], "First strike, hexproof");
-        assert_eq!(c.flavor, "");
+/// Like [`name_find_unique`] but sends the requests through the given client
+pub async fn name_find_unique_with(
+    client: &MtgClient,
+    name: &str,
+) -> Result<MultiCards, MTGCardError> {
+    let found = MultiCards::from_response(client.card_exact_name_info(name).await?).await?;
+    Ok(unique_by_date(
+        found.cards,
+        &sets_find_with(client).await?.sets,
+    ))
+}
 
-        // Check is Error
-        let a = id_find(173132123).await;
-        assert!(a.is_err());
+/// Keep the printing of each card from the most recently released of `sets`
+pub(crate) fn unique_by_date(cards: Vec<Card>, sets: &[Set]) -> MultiCards {
+    let mut dated = printings_by_date(cards, sets);
+    // Undated printings first, so any dated one is preferred as the latest
+    dated.sort_by(|(_, a), (_, b)| a.cmp(b));
+    MultiCards {
+        cards: latest_printings(dated.into_iter().map(|(card, _)| card).collect()),
     }
+}
 
-    #[tokio::test]
-    async fn find_card_name() {
-        // Get a known card Narset, Enlightened Master
-        let a = name_find("Narset, Enlightened Master").await;
-        assert!(a.is_ok());
+/// Keep only the last printing of each card name, in the order those printings appear
+fn latest_printings(cards: Vec<Card>) -> Vec<Card> {
+    let mut seen = HashSet::new();
+    let mut unique: Vec<Card> = cards
+        .into_iter()
+        .rev()
+        .filter(|c| seen.insert(c.name.clone()))
+        .collect();
+    unique.reverse();
+    unique
+}
 
-        // Check it returned the correct card
-        let b = id_find(386616).await;
-        assert_eq!(a.unwrap().cards[0].name, b.unwrap().card.name);
+/// Every printing of the card with the given name, paired with the release date of its set
+///
+/// One request finds the printings and another the sets. Printings are sorted from the
+/// earliest release onwards, those whose set has no known date come last with `None`.
+pub async fn printing_history(name: &str) -> Result<Vec<(Card, Option<String>)>, MTGCardError> {
+    let printings = name_find(name).await?;
+    Ok(printings_by_date(printings.cards, &sets_find().await?.sets))
+}
 
-        // Check it returns an error
+/// Like [`printing_history`] but sends the requests through the given client
+pub async fn printing_history_with(
+    client: &MtgClient,
+    name: &str,
+) -> Result<Vec<(Card, Option<String>)>, MTGCardError> {
+    let printings = MultiCards::from_response(client.card_exact_name_info(name).await?).await?;
+    Ok(printings_by_date(
+        printings.cards,
+        &sets_find_with(client).await?.sets,
+    ))
+}
+
+/// Pair each printing with the release date of its set and sort them by it, undated last
+pub(crate) fn printings_by_date(cards: Vec<Card>, sets: &[Set]) -> Vec<(Card, Option<String>)> {
+    let dates: HashMap<&str, &str> = sets
+        .iter()
+        .filter(|s| !s.release_date().is_empty())
+        .map(|s| (s.code(), s.release_date()))
+        .collect();
+    let mut history: Vec<(Card, Option<String>)> = cards
+        .into_iter()
+        .map(|c| {
+            let date = dates.get(c.set.as_str()).map(|d| d.to_string());
+            (c, date)
+        })
+        .collect();
+    // Dates are `YYYY-MM-DD` so they sort as strings
+    history.sort_by(|(_, a), (_, b)| (a.is_none(), a).cmp(&(b.is_none(), b)));
+    history
+}
+
+/// Every card printed in any of the sets with the given codes, as one collection
+///
+/// The codes are checked against [`sets_find`] first, erroring with
+/// [`MTGCardError::UnknownSets`] listing those that match no set. Every page of each set
+/// is then fetched, up to [`DEFAULT_CONCURRENCY`] sets at once, and merged in the order
+/// of `codes`. A card reprinted across the sets appears once per printing, unless `dedup`
+/// is set to keep only its first printing as [`MultiCards::dedup_names`] does.
+pub async fn cards_in_sets(codes: &[&str], dedup: bool) -> Result<MultiCards, MTGCardError> {
+    let sets = sets_find().await?;
+    let mut cards = cards_in_listed_sets(CardQuery::new(), codes, &sets.sets).await?;
+    if dedup {
+        cards.dedup_names();
+    }
+    Ok(cards)
+}
+
+/// Like [`cards_in_sets`] but sends the requests through the given client
+pub async fn cards_in_sets_with(
+    client: &MtgClient,
+    codes: &[&str],
+    dedup: bool,
+) -> Result<MultiCards, MTGCardError> {
+    let sets = sets_find_with(client).await?;
+    let mut cards = cards_in_listed_sets(client.query(), codes, &sets.sets).await?;
+    if dedup {
+        cards.dedup_names();
+    }
+    Ok(cards)
+}
+
+/// Fetch every card of the sets with the given codes, checking them against `sets` first
+pub(crate) async fn cards_in_listed_sets(
+    query: CardQuery<'_>,
+    codes: &[&str],
+    sets: &[Set],
+) -> Result<MultiCards, MTGCardError> {
+    let mut wanted: Vec<&str> = Vec::new();
+    let mut unknown: Vec<String> = Vec::new();
+    for code in codes {
+        match sets
+            .iter()
+            .find(|s| s.code().eq_ignore_ascii_case(code.trim()))
+        {
+            Some(set) if !wanted.contains(&set.code()) => wanted.push(set.code()),
+            Some(_) => (),
+            None => unknown.push(code.trim().to_owned()),
+        }
+    }
+    if !unknown.is_empty() {
+        return Err(MTGCardError::UnknownSets { codes: unknown });
+    }
+
+    let query = &query;
+    let parts: Vec<MultiCards> = stream::iter(wanted)
+        .map(|code| query_find_all(query.clone().set(code)))
+        .buffered(DEFAULT_CONCURRENCY)
+        .try_collect()
+        .await?;
+    Ok(MultiCards::merge_all(parts))
+}
+
+/// Every card from the sets released after `last_sync`, a date written as `YYYY-MM-DD`
+///
+/// For keeping a local copy up to date without downloading everything again. Sets are
+/// found with [`sets_find`] and fetched as [`cards_in_sets`] does, oldest release first.
+/// If no set is newer the result has no cards rather than erroring. Errors with
+/// [`MTGCardError::InvalidDate`] before any request if `last_sync` is not a date.
+pub async fn cards_since(last_sync: &str) -> Result<MultiCards, MTGCardError> {
+    check_date(last_sync)?;
+    let sets = sets_find().await?;
+    cards_released_after(CardQuery::new(), last_sync, &sets.sets).await
+}
+
+/// Like [`cards_since`] but sends the requests through the given client
+pub async fn cards_since_with(
+    client: &MtgClient,
+    last_sync: &str,
+) -> Result<MultiCards, MTGCardError> {
+    check_date(last_sync)?;
+    let sets = sets_find_with(client).await?;
+    cards_released_after(client.query(), last_sync, &sets.sets).await
+}
+
+/// Error unless the date is written as `YYYY-MM-DD` and names a day of the calendar
+fn check_date(date: &str) -> Result<(), MTGCardError> {
+    let invalid = || MTGCardError::InvalidDate {
+        date: date.to_owned(),
+    };
+    let parts: Vec<&str> = date.split('-').collect();
+    let [year, month, day] = parts[..] else {
+        return Err(invalid());
+    };
+    let number = |part: &str, len: usize| match part.len() == len
+        && part.bytes().all(|b| b.is_ascii_digit())
+    {
+        true => part.parse::<u32>().ok(),
+        false => None,
+    };
+    let (Some(year), Some(month), Some(day)) = (number(year, 4), number(month, 2), number(day, 2))
+    else {
+        return Err(invalid());
+    };
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return Err(invalid()),
+    };
+    match (1..=days).contains(&day) {
+        true => Ok(()),
+        false => Err(invalid()),
+    }
+}
+
+/// Fetch every card from the sets in `sets` released after the date, oldest set first
+pub(crate) async fn cards_released_after(
+    query: CardQuery<'_>,
+    date: &str,
+    sets: &[Set],
+) -> Result<MultiCards, MTGCardError> {
+    let mut newer: Vec<&Set> = sets.iter().filter(|s| s.release_date() > date).collect();
+    newer.sort_by_key(|s| s.release_date());
+    let codes: Vec<&str> = newer.iter().map(|s| s.code()).collect();
+    match codes.is_empty() {
+        true => Ok(MultiCards { cards: Vec::new() }),
+        false => cards_in_listed_sets(query, &codes, sets).await,
+    }
+}
+
+/// One printing of every card that may be played in a deck of the given format
+///
+/// For a question such as "what can I play in Pioneer?". Every page of cards with a
+/// legality in the format is fetched, keeping cards that are `Legal` or `Restricted` in it.
+/// Printings from funny and digital only sets, see [`Set::is_casual`], are left out and
+/// each card is kept once, by name, as soon as its first printing is seen. That takes a
+/// request for the sets, unless [`warm_sets_cache`] has run, and one per page, several
+/// hundred for the larger formats.
+pub async fn format_pool(format: &str) -> Result<Vec<Card>, MTGCardError> {
+    let sets = sets_find().await?;
+    format_pool_in(CardQuery::new(), format, &sets.sets).await
+}
+
+/// Like [`format_pool`] but sends the requests through the given client
+pub async fn format_pool_with(client: &MtgClient, format: &str) -> Result<Vec<Card>, MTGCardError> {
+    let sets = sets_find_with(client).await?;
+    format_pool_in(client.query(), format, &sets.sets).await
+}
+
+/// Page through the cards of a format, keeping the first playable printing of each name
+pub(crate) async fn format_pool_in(
+    query: CardQuery<'_>,
+    format: &str,
+    sets: &[Set],
+) -> Result<Vec<Card>, MTGCardError> {
+    let casual: HashSet<&str> = sets
+        .iter()
+        .filter(|s| s.is_casual())
+        .map(|s| s.code())
+        .collect();
+    let query = query.game_format(format);
+
+    let mut names = HashSet::new();
+    let mut pool = Vec::new();
+    let mut seen = 0;
+    for page in 1_u32.. {
+        let res = query
+            .clone()
+            .page(page)
+            .page_size(mtg_api::MAX_PAGE_SIZE)
+            .send()
+            .await?;
+        let (found, header) = match MultiCards::from_response_with_header(res).await {
+            Err(MTGCardError::NoCardError {}) => break,
+            found => found?,
+        };
+        seen += found.cards.len();
+        // Only the cards kept are held on to, so memory grows with the pool not the crawl
+        pool.extend(found.cards.into_iter().filter(|c| {
+            c.playable_in(format)
+                && !casual.contains(c.set.as_str())
+                && names.insert(c.name.clone())
+        }));
+        if seen >= header.total_count() {
+            break;
+        }
+    }
+    Ok(pool)
+}
+
+/// Takes a set code and returns a random booster pack of it deserialised into [`MultiCards`]
+///
+/// Errors with [`mtg_api::APIError::NotFound`], wrapped in [`MTGCardError::WrappedAPI`],
+/// if there is no set with that code, as [`id_find`] does for an unknown id.
+pub async fn booster_find(set_code: &str) -> Result<MultiCards, MTGCardError> {
+    MultiCards::from_response(mtg_api::generate_booster(set_code).await?).await
+}
+
+/// Booster packs opened by [`open_packs`] along with the requests that failed
+#[derive(Clone, Debug, Default)]
+pub struct OpenedPacks {
+    /// Every pack that was opened, in the order their requests finished
+    pub packs: Vec<MultiCards>,
+    /// The error of each pack that could not be opened
+    pub failed: Vec<MTGCardError>,
+}
+
+/// Takes a set code and opens `count` random booster packs of it, as for a sealed pool
+///
+/// Up to [`DEFAULT_CONCURRENCY`] packs are requested at once and rate limited requests are
+/// retried up to [`CRAWL_RETRIES`] times. A pack whose request still fails is recorded in
+/// [`OpenedPacks::failed`] rather than ending the others, so fewer than `count` packs may
+/// be opened. An unknown set code fails every pack with [`mtg_api::APIError::NotFound`].
+pub async fn open_packs(set_code: &str, count: usize) -> OpenedPacks {
+    let client = MtgClient::new().with_max_retries(CRAWL_RETRIES);
+    open_packs_with(&client, set_code, count).await
+}
+
+/// Like [`open_packs`] but opens the packs through the given client and its retry policy
+pub async fn open_packs_with(client: &MtgClient, set_code: &str, count: usize) -> OpenedPacks {
+    let opened: Vec<Result<MultiCards, MTGCardError>> = stream::iter(0..count)
+        .map(|_| async move {
+            MultiCards::from_response(client.generate_booster(set_code).await?).await
+        })
+        .buffer_unordered(DEFAULT_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut result = OpenedPacks::default();
+    for pack in opened {
+        match pack {
+            Ok(pack) => result.packs.push(pack),
+            Err(e) => result.failed.push(e),
+        }
+    }
+    result
+}
+
+/// Takes card types to find and returns every card matching them
+///
+/// When `any` is set a card needs only one of the types, e.g. instants or sorceries. Every
+/// page of each type is then fetched in turn, one request per page per type, and merged
+/// leaving out cards already found. Otherwise a card must have every type, which the API
+/// answers directly, one request per page of matches. Errors with
+/// [`MTGCardError::NoCardError`] if no card matches.
+pub async fn types_find(types: &[&str], any: bool) -> Result<MultiCards, MTGCardError> {
+    types_in(CardQuery::new(), types, any).await
+}
+
+/// Like [`types_find`] but sends the requests through the given client
+pub async fn types_find_with(
+    client: &MtgClient,
+    types: &[&str],
+    any: bool,
+) -> Result<MultiCards, MTGCardError> {
+    types_in(client.query(), types, any).await
+}
+
+/// Fetch every card with all of the types, or with any of them merged by card id
+async fn types_in(
+    query: CardQuery<'_>,
+    types: &[&str],
+    any: bool,
+) -> Result<MultiCards, MTGCardError> {
+    let found = match any {
+        false => query_find_all(query.types(types)).await?,
+        true => {
+            let mut seen = HashSet::new();
+            let mut cards = Vec::new();
+            for card_type in types {
+                let found = query_find_all(query.clone().types(&[card_type])).await?;
+                cards.extend(
+                    found
+                        .cards
+                        .into_iter()
+                        .filter(|c| c.id.is_empty() || seen.insert(c.id.clone())),
+                );
+            }
+            MultiCards { cards }
+        }
+    };
+    match found.cards.is_empty() {
+        true => Err(MTGCardError::NoCardError {}),
+        false => Ok(found),
+    }
+}
+
+/// Takes a page number to fetch cards from and returns them deserialised into [`MultiCards`]
+pub async fn page_find(number: u64) -> Result<MultiCards, MTGCardError> {
+    let index = number.to_string();
+    MultiCards::from_response(mtg_api::card_page(&index).await?).await
+}
+
+/// Like [`page_find`] but also returns the pagination headers of the page, in one request
+pub async fn page_find_with_header(number: u64) -> Result<(MultiCards, MTGHeader), MTGCardError> {
+    let index = number.to_string();
+    MultiCards::from_response_with_header(mtg_api::card_page(&index).await?).await
+}
+
+/// Number of cards in the API, without downloading more than one of them
+pub async fn total_count() -> Result<usize, MTGCardError> {
+    total_count_of(CardQuery::new()).await
+}
+
+/// Number of cards matching the query, read from the `Total-Count` header of a one card page
+pub async fn total_count_of(query: CardQuery<'_>) -> Result<usize, MTGCardError> {
+    let res = query.page_size(1).send().await?;
+    Ok(MTGHeader::from_response(&res).await?.total_count())
+}
+
+/// Every card matching the query, fetched a page at a time until its `Total-Count` is reached
+///
+/// Any page or page size set on the query is replaced. Nothing matching gives no cards
+/// rather than an error.
+pub async fn query_find_all(query: CardQuery<'_>) -> Result<MultiCards, MTGCardError> {
+    let mut cards = Vec::new();
+    for page in 1_u32.. {
+        let res = query
+            .clone()
+            .page(page)
+            .page_size(mtg_api::MAX_PAGE_SIZE)
+            .send()
+            .await?;
+        match MultiCards::from_response_with_header(res).await {
+            Ok((found, header)) => {
+                cards.extend(found.cards);
+                if cards.len() >= header.total_count() {
+                    break;
+                }
+            }
+            Err(MTGCardError::NoCardError {}) => break,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(MultiCards { cards })
+}
+
+/// Every card with a converted mana cost between `min` and `max`, inclusive
+///
+/// Cards within the bound matching fewer cards are fetched page by page and filtered on the
+/// other, so fractional costs are kept. Errors with [`MTGCardError::NoCardError`] if none match.
+pub async fn cmc_between_find(
+    min: Option<f32>,
+    max: Option<f32>,
+) -> Result<MultiCards, MTGCardError> {
+    cmc_between(CardQuery::new(), min, max).await
+}
+
+/// Like [`cmc_between_find`] but sends the requests through the given client
+pub async fn cmc_between_find_with(
+    client: &MtgClient,
+    min: Option<f32>,
+    max: Option<f32>,
+) -> Result<MultiCards, MTGCardError> {
+    cmc_between(client.query(), min, max).await
+}
+
+/// Search an exact whole cost, or fetch every card within one bound and keep those within both
+async fn cmc_between(
+    query: CardQuery<'_>,
+    min: Option<f32>,
+    max: Option<f32>,
+) -> Result<MultiCards, MTGCardError> {
+    let mut found = match (min, max) {
+        (Some(min), Some(max)) if min == max && min >= 0.0 && min.fract() == 0.0 => {
+            query_find_all(query.cmc(min as u32)).await?
+        }
+        (Some(min), Some(max)) => {
+            let at_most = query.clone().cmc_at_most(max);
+            let at_least = query.cmc_at_least(min);
+            let fewer_at_most =
+                total_count_of(at_most.clone()).await? <= total_count_of(at_least.clone()).await?;
+            match fewer_at_most {
+                true => query_find_all(at_most).await?,
+                false => query_find_all(at_least).await?,
+            }
+        }
+        (None, Some(max)) => query_find_all(query.cmc_at_most(max)).await?,
+        (Some(min), None) => query_find_all(query.cmc_at_least(min)).await?,
+        (None, None) => query_find_all(query).await?,
+    };
+    found
+        .cards
+        .retain(|c| min.is_none_or(|min| c.cmc() >= min) && max.is_none_or(|max| c.cmc() <= max));
+    match found.cards.is_empty() {
+        true => Err(MTGCardError::NoCardError {}),
+        false => Ok(found),
+    }
+}
+
+/// Every card with a whole converted mana cost from `min` to `max`, matching `extra` too
+///
+/// The API only filters on an exact `cmc`, so each cost in the range is searched on its own
+/// with the filters of `extra`, every page of it fetched, and the results are merged
+/// leaving out cards already found. That is one request per page per cost, so a range
+/// may cover at most [`MAX_CMC_RANGE`] costs; a wider or reversed range errors with
+/// [`mtg_api::APIError::UnsupportedQuery`] before anything is sent. Unlike
+/// [`cmc_between_find`] cards with a fractional cost are never found. Any `cmc`, page or
+/// page size set on `extra` is replaced.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), mtg_cards::MTGCardError> {
+/// let red = mtg_api::CardQuery::new().colors(&["red"]).types(&["creature"]);
+/// let two_to_four_drops = mtg_cards::cmc_range_find(2, 4, red).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn cmc_range_find(
+    min: u32,
+    max: u32,
+    extra: CardQuery<'_>,
+) -> Result<MultiCards, MTGCardError> {
+    if min > max || max - min >= MAX_CMC_RANGE {
+        return Err(mtg_api::APIError::UnsupportedQuery {
+            reason: format!(
+                "a cmc range covers at most {} costs from min to max, got {min} to {max}",
+                MAX_CMC_RANGE
+            ),
+        }
+        .into());
+    }
+
+    let mut seen = HashSet::new();
+    let mut cards = Vec::new();
+    for cmc in min..=max {
+        let found = query_find_all(extra.clone().cmc(cmc)).await?;
+        cards.extend(
+            found
+                .cards
+                .into_iter()
+                .filter(|c| c.id.is_empty() || seen.insert(c.id.clone())),
+        );
+    }
+    Ok(MultiCards { cards })
+}
+
+/// Takes a range of page numbers and returns every card on them, in page order
+///
+/// Up to [`DEFAULT_CONCURRENCY`] pages are requested at once. If any page fails the
+/// whole fetch stops with its error.
+pub async fn pages_find(range: RangeInclusive<u64>) -> Result<Vec<Card>, MTGCardError> {
+    pages_find_concurrent(range, DEFAULT_CONCURRENCY).await
+}
+
+/// Like [`pages_find`] but with at most `concurrency` pages requested at once
+pub async fn pages_find_concurrent(
+    range: RangeInclusive<u64>,
+    concurrency: usize,
+) -> Result<Vec<Card>, MTGCardError> {
+    let mut pages: Vec<(u64, MultiCards)> = stream::iter(range)
+        .map(|number| async move { Ok::<_, MTGCardError>((number, page_find(number).await?)) })
+        .buffer_unordered(concurrency.max(1))
+        .try_collect()
+        .await?;
+    pages.sort_by_key(|(number, _)| *number);
+    Ok(pages.into_iter().flat_map(|(_, page)| page.cards).collect())
+}
+
+/// Count the distinct card names across every page of the API
+///
+/// `Total-Count` counts every printing, this crawls all pages to count each card once.
+/// That takes close to a thousand requests, most of the hourly rate limit, so the result
+/// should be cached rather than fetched repeatedly. Rate limited pages are retried up to
+/// [`CRAWL_RETRIES`] times, waiting for the `Retry-After` the API asks for.
+///
+/// The API has no way to return only some fields of a card, so whole pages are fetched.
+/// Only the names are deserialised from them, the rest of each card is skipped.
+pub async fn distinct_card_count() -> Result<usize, MTGCardError> {
+    distinct_card_count_with(&MtgClient::new().with_max_retries(CRAWL_RETRIES)).await
+}
+
+/// Like [`distinct_card_count`] but crawls through the given client and its retry policy
+pub async fn distinct_card_count_with(client: &MtgClient) -> Result<usize, MTGCardError> {
+    #[derive(Deserialize)]
+    struct Named {
+        name: String,
+    }
+    #[derive(Deserialize)]
+    struct NamesPage {
+        cards: Vec<Named>,
+    }
+
+    let mut names = HashSet::new();
+    for page in 1_u64.. {
+        let res = client.card_page(&page.to_string()).await?;
+        match mtg_api::check_for_empty(res).await? {
+            Some(json) => {
+                let page: NamesPage = serde_json::from_str(&json)?;
+                names.extend(page.cards.into_iter().map(|c| c.name));
+            }
+            None => break,
+        }
+    }
+    Ok(names.len())
+}
+
+/// Cards fetched by [`download_all_resilient`] along with the pages that failed
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ResilientDownload {
+    /// Every card on the pages that were fetched, in page order
+    pub cards_written: Vec<Card>,
+    /// Number of each page that failed, in order, to retry with [`page_find`]
+    pub failed_pages: Vec<u64>,
+}
+
+/// Download every card in the API, carrying on past pages that fail
+///
+/// The first page gives the number of pages from its headers, so the download errors if it
+/// fails. Up to [`DEFAULT_CONCURRENCY`] of the other pages are then requested at once and
+/// rate limited pages are retried up to [`CRAWL_RETRIES`] times. A page that still fails
+/// is recorded in [`ResilientDownload::failed_pages`] rather than ending the download.
+pub async fn download_all_resilient() -> Result<ResilientDownload, MTGCardError> {
+    download_all_resilient_with(&MtgClient::new().with_max_retries(CRAWL_RETRIES)).await
+}
+
+/// Like [`download_all_resilient`] but crawls through the given client and its retry policy
+pub async fn download_all_resilient_with(
+    client: &MtgClient,
+) -> Result<ResilientDownload, MTGCardError> {
+    let (first, header) =
+        MultiCards::from_response_with_header(client.card_page("1").await?).await?;
+    let last_page = header.total_count().div_ceil(header.page_size().max(1)) as u64;
+
+    let rest: Vec<(u64, Result<MultiCards, MTGCardError>)> = stream::iter(2..=last_page)
+        .map(|number| async move {
+            let page = async {
+                MultiCards::from_response(client.card_page(&number.to_string()).await?).await
+            };
+            (number, page.await)
+        })
+        .buffered(DEFAULT_CONCURRENCY)
+        .collect()
+        .await;
+
+    let mut download = ResilientDownload {
+        cards_written: first.cards,
+        failed_pages: Vec::new(),
+    };
+    for (number, page) in rest {
+        match page {
+            Ok(page) => download.cards_written.extend(page.cards),
+            // The last page can empty out if cards are removed mid-crawl
+            Err(MTGCardError::NoCardError {}) => (),
+            Err(_) => download.failed_pages.push(number),
+        }
+    }
+    Ok(download)
+}
+
+/// Fetch every face of a multi-faced card, such as the back face of a transform card
+///
+/// Each face is looked up by name, preferring the printing from the same set as `card`.
+/// Cards with a single face are returned as is.
+pub async fn resolve_faces(card: &Card) -> Result<Vec<Card>, MTGCardError> {
+    if card.names.len() < 2 {
+        return Ok(vec![card.clone()]);
+    }
+
+    let mut faces = Vec::with_capacity(card.names.len());
+    for name in &card.names {
+        if *name == card.name {
+            faces.push(card.clone());
+            continue;
+        }
+        let printings = name_find(name).await?.cards;
+        let face = printings
+            .iter()
+            .find(|c| c.set_name == card.set_name)
+            .or(printings.first())
+            .ok_or(MTGCardError::NoCardError {})?;
+        faces.push(face.clone());
+    }
+    Ok(faces)
+}
+
+/// Returns the spells whose mana cost needs a color that none of `mana_colors` provide
+///
+/// Hybrid symbols are castable if either half is available, generic costs never block a spell.
+pub fn uncastable_cards<'a>(mana_colors: &[Color], spells: &'a [Card]) -> Vec<&'a Card> {
+    spells
+        .iter()
+        .filter(|c| !ManaCost::parse(&c.mana_cost).castable_with(mana_colors))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mtg_api::mock;
+    use serde::de::Error;
+
+    #[tokio::test]
+    async fn find_card_id() {
+        // Get a known card Narset, Enlightened Master
+        let a = id_find(386616).await;
+        assert!(a.is_ok());
+
+        // Check Individual fields of card struct
+        let c = &a.unwrap().card;
+        assert_eq!(c.name, "Narset, Enlightened Master");
+        assert_eq!(c.mana_cost, "{3}{U}{R}{W}");
+        assert_eq!(c.type_field, "Legendary Creature — Human Monk");
+        assert_eq!(c.rarity, "Mythic");
+        assert_eq!(c.set_name, "Khans of Tarkir");
+        assert_eq!(&c.text[..22], "First strike, hexproof");
+        assert_eq!(c.flavor, "");
+        assert_eq!(c.cmc(), 6.0);
+        assert_eq!(c.supertypes(), ["Legendary"]);
+        assert_eq!(c.types(), ["Creature"]);
+        assert_eq!(c.subtypes(), ["Human", "Monk"]);
+        assert!(!c.id().is_empty());
+        assert!(c.id().chars().all(|ch| ch.is_ascii_hexdigit() || ch == '-'));
+        assert_eq!(c.set(), "KTK");
+        assert!(c.number().parse::<u32>().is_ok());
+        assert!(c.printings().len() > 1);
+        assert!(c.printings().contains(&"KTK".to_owned()));
+        assert!(c.rulings().iter().any(|r| !r.text.is_empty()));
+        assert!(c.name_in_language("German").is_some_and(|n| !n.is_empty()));
+
+        // Check is Error
+        let a = id_find(173132123).await;
+        assert_eq!(
+            a.unwrap_err().status_code(),
+            Some(reqwest::StatusCode::NOT_FOUND)
+        );
+    }
+
+    #[tokio::test]
+    async fn find_card_id_mocked() {
+        let card = r#"{"card":{"name":"Mock Card","manaCost":"{1}{U}","cmc":2}}"#;
+        let server = mock::serve(vec![mock::json(card)]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+
+        let found = id_find_with(&client, 42).await.unwrap().card;
+        assert_eq!(found.name, "Mock Card");
+        assert_eq!(found.mana_cost, "{1}{U}");
+        assert_eq!(found.cmc(), 2.0);
+        assert_eq!(server.paths(), vec!["/cards/42"]);
+
+        let server = mock::serve(vec![mock::respond("404 Not Found", &[], "")]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        assert!(matches!(
+            id_find_with(&client, 999999999).await,
+            Err(MTGCardError::WrappedAPI {
+                e: mtg_api::APIError::NotFound {}
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn count_matching_cards() {
+        let headers = [
+            ("content-type", "application/json"),
+            ("page-size", "1"),
+            ("count", "1"),
+            ("total-count", "269"),
+            ("ratelimit-limit", "1000"),
+            ("ratelimit-remaining", "999"),
+        ];
+        let page = mock::respond("200 OK", &headers, r#"{"cards":[{"name":"A"}]}"#);
+        let server = mock::serve(vec![page]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        assert_eq!(
+            total_count_of(client.query().set("KTK")).await.unwrap(),
+            269
+        );
+        assert_eq!(server.paths(), vec!["/cards?set=KTK&pageSize=1"]);
+    }
+
+    #[tokio::test]
+    async fn cards_and_header_in_one_request() {
+        let headers = [
+            ("content-type", "application/json"),
+            ("page-size", "100"),
+            ("count", "1"),
+            ("total-count", "1"),
+            ("ratelimit-limit", "5000"),
+            ("ratelimit-remaining", "4321"),
+        ];
+        let page = mock::respond("200 OK", &headers, r#"{"cards":[{"name":"Only"}]}"#);
+        let server = mock::serve(vec![page]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let res = client.card_page("1").await.unwrap();
+        let (cards, header) = MultiCards::from_response_with_header(res).await.unwrap();
+        assert_eq!(cards.cards[0].name, "Only");
+        assert_eq!(header.ratelimit_limit(), 5000);
+        assert_eq!(header.ratelimit_remaining(), 4321);
+        assert_eq!(server.paths().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn count_all_cards() {
+        assert!(total_count().await.unwrap() > 90000);
+    }
+
+    #[tokio::test]
+    async fn error_status_code() {
+        let server = mock::serve(vec![mock::respond("404 Not Found", &[], "")]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let err = id_find_with(&client, 999999999).await.unwrap_err();
+        assert_eq!(err.status_code(), Some(reqwest::StatusCode::NOT_FOUND));
+
+        let server = mock::serve(vec![mock::respond("503 Service Unavailable", &[], "")]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let err = id_find_with(&client, 386616).await.unwrap_err();
+        assert_eq!(
+            err.status_code(),
+            Some(reqwest::StatusCode::SERVICE_UNAVAILABLE)
+        );
+
+        assert_eq!(MTGCardError::NoCardError {}.status_code(), None);
+    }
+
+    #[tokio::test]
+    async fn find_card_name() {
+        // Get a known card Narset, Enlightened Master
+        let a = name_find("Narset, Enlightened Master").await;
+        assert!(a.is_ok());
+
+        // Check it returned the correct card
+        let b = id_find(386616).await;
+        assert_eq!(a.unwrap().cards[0].name, b.unwrap().card.name);
+
+        // Check it returns an error
         let a = name_find("Narset, Unenlightened Student").await;
         assert!(a.is_err());
     }
@@ -190,6 +1674,7 @@ mod tests {
             set_name: "set".to_string(),
             text: "body".to_string(),
             flavor: "flavour".to_string(),
+            ..Default::default()
         };
         let display = "**************************************************\nname                                          mana\n--------------------------------------------------\ntype                                        rarity\n--------------------------------------------------\nbody\nflavour\n                                               set\n**************************************************\n".to_string();
         let blank_display = format!("{}", blank);
@@ -209,10 +1694,1300 @@ mod tests {
         assert!(page_res.is_err());
     }
 
+    #[tokio::test]
+    async fn find_page_with_header() {
+        let (page, header) = page_find_with_header(1).await.unwrap();
+        assert_eq!(page.cards.len(), 100);
+        assert_eq!(header.count(), page.cards.len());
+        assert!(header.total_count() > 90000);
+    }
+
+    #[tokio::test]
+    async fn find_pages_concurrently() {
+        let cards = pages_find(1..=3).await.unwrap();
+        assert_eq!(cards.len(), 300);
+        assert_eq!(cards[0].name, "Ancestor's Chosen");
+
+        let second = page_find(2).await.unwrap();
+        assert_eq!(cards[100..200], second.cards[..]);
+
+        assert!(pages_find_concurrent(1..=2, 1).await.is_ok());
+        assert!(pages_find(u64::MAX - 1..=u64::MAX).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn find_ids_in_order() {
+        let cards = ids_find(&[386616, 3]).await.unwrap();
+        let names: Vec<&str> = cards.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Narset, Enlightened Master", "Black Lotus"]);
+
+        assert!(ids_find(&[]).await.unwrap().is_empty());
+        assert!(ids_find(&[386616, 173132123]).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn find_page_ordered() {
+        let res = mtg_api::card_page_ordered("1", "name").await.unwrap();
+        let page = MultiCards::from_response(res).await.unwrap();
+        assert!(!page.cards.is_empty());
+        assert!(page.cards.windows(2).all(|w| w[0].name <= w[1].name));
+    }
+
+    #[tokio::test]
+    async fn resolve_card_faces() {
+        // A normal card only has itself as a face
+        let single = Card {
+            name: "Lightning Bolt".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(resolve_faces(&single).await.unwrap(), vec![single]);
+
+        // Delver of Secrets transforms into Insectile Aberration
+        let delver = &name_find("Delver of Secrets").await.unwrap().cards[0];
+        let faces = resolve_faces(delver).await.unwrap();
+        assert_eq!(faces.len(), 2);
+        assert_eq!(faces[0].name, "Delver of Secrets");
+        assert_eq!(faces[1].name, "Insectile Aberration");
+    }
+
+    #[test]
+    fn group_multipart_halves() {
+        let half = |name: &str, set_name: &str| Card {
+            name: name.to_string(),
+            set_name: set_name.to_string(),
+            names: vec!["Fire".to_string(), "Ice".to_string()],
+            layout: "split".to_string(),
+            ..Default::default()
+        };
+        let bolt = Card {
+            name: "Lightning Bolt".to_string(),
+            layout: "normal".to_string(),
+            ..Default::default()
+        };
+        let results = MultiCards {
+            cards: vec![
+                half("Ice", "Apocalypse"),
+                bolt.clone(),
+                half("Fire", "Apocalypse"),
+                half("Fire", "Modern Masters"),
+            ],
+        };
+
+        let groups = results.group_multipart();
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups[0][0].name, "Fire");
+        assert_eq!(groups[0][1].name, "Ice");
+        assert_eq!(groups[1], vec![bolt]);
+        assert_eq!(groups[2].len(), 1);
+    }
+
+    #[tokio::test]
+    async fn group_adventure_halves() {
+        let mut results = name_find("Brazen Borrower").await.unwrap();
+        results
+            .cards
+            .extend(name_find("Petty Theft").await.unwrap().cards);
+
+        let groups = results.group_multipart();
+        assert!(!groups.is_empty());
+        for group in groups {
+            assert_eq!(group.len(), 2);
+            assert_eq!(group[0].name, "Brazen Borrower");
+            assert_eq!(group[1].name, "Petty Theft");
+        }
+    }
+
+    #[test]
+    fn display_without_color() {
+        let card = Card {
+            name: "Brazen Borrower".to_string(),
+            flavor: "A thief of time.".to_string(),
+            ..Default::default()
+        };
+        let plain = card.display_plain();
+        assert!(!plain.contains('\x1b'));
+        assert!(plain.contains("\nA thief of time.\n"));
+    }
+
+    #[test]
+    fn display_width() {
+        let card = Card {
+            name: "Narset, Enlightened Master".to_string(),
+            text: "First strike, hexproof. Whenever Narset attacks, exile the top four cards."
+                .to_string(),
+            ..Default::default()
+        };
+        let narrow = card.display_with_width(30).to_string();
+        let wide = card.display_with_width(80).to_string();
+        assert_eq!(narrow.lines().next().unwrap(), "*".repeat(30));
+        assert_eq!(wide.lines().next().unwrap(), "*".repeat(80));
+        assert!(narrow.lines().all(|l| l.chars().count() <= 30));
+
+        // The default width is unchanged
+        assert_eq!(card.to_string(), card.display_with_width(50).to_string());
+    }
+
+    #[test]
+    fn display_multiple_cards() {
+        let card = |name: &str| Card {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        let both = MultiCards {
+            cards: vec![card("Fire"), card("Ice")],
+        };
+        let shown = both.to_string();
+        assert!(shown.contains("Fire"));
+        assert!(shown.contains("Ice"));
+        assert!(shown.find("Fire") < shown.find("Ice"));
+
+        assert_eq!(MultiCards { cards: vec![] }.to_string(), "");
+    }
+
+    #[test]
+    fn display_normalized_text() {
+        let card = Card {
+            name: "Brazen Borrower".to_string(),
+            text: "Flash\n\n\nFlying  \n  Brazen Borrower can block only creatures with flying."
+                .to_string(),
+            ..Default::default()
+        };
+        let options = DisplayOptions {
+            normalize_text: true,
+            ..Default::default()
+        };
+        let normalized = card.display_with(options).to_string();
+        assert!(normalized.contains("\nFlash\n\nFlying\nBrazen Borrower can block only"));
+
+        // Default display keeps the blank lines as given
+        assert!(card.to_string().contains("\nFlash\n\n\nFlying\nBrazen"));
+    }
+
+    #[test]
+    fn skip_nameless_cards() {
+        let json = r#"{"cards":[{"name":"Narset, Enlightened Master"},{"type":"Emblem"}]}"#;
+
+        let kept = MultiCards::parse(json, NamelessCards::Keep).unwrap();
+        assert_eq!(kept.cards.len(), 2);
+        assert_eq!(kept.cards[1].name, "");
+
+        let skipped = MultiCards::parse(json, NamelessCards::Skip).unwrap();
+        assert_eq!(skipped.cards.len(), 1);
+        assert_eq!(skipped.cards[0].name, "Narset, Enlightened Master");
+
+        let only_nameless = r#"{"cards":[{"type":"Emblem"}]}"#;
+        assert!(MultiCards::parse(only_nameless, NamelessCards::Skip).is_err());
+    }
+
+    #[tokio::test]
+    async fn open_booster() {
+        let booster = booster_find("KTK").await.unwrap();
+        assert!((14..=16).contains(&booster.cards.len()));
+        assert!(booster
+            .cards
+            .iter()
+            .all(|c| c.set_name == "Khans of Tarkir"));
+
+        match booster_find("ZZZ").await {
+            Err(MTGCardError::WrappedAPI {
+                e: mtg_api::APIError::NotFound {},
+            }) => (),
+            other => panic!("Expected NotFound, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn open_packs_skips_failures() {
+        let pack = mock::json(r#"{"cards":[{"name":"Mountain"},{"name":"Shock"}]}"#);
+        let failed = mock::respond("500 Internal Server Error", &[], "");
+        let server = mock::serve(vec![pack.clone(), failed.clone(), pack]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let opened = open_packs_with(&client, "ktk", 3).await;
+        assert_eq!(opened.packs.len(), 2);
+        assert!(opened.packs.iter().all(|p| p.cards.len() == 2));
+        assert_eq!(opened.failed.len(), 1);
+        assert_eq!(
+            opened.failed[0].status_code(),
+            Some(reqwest::StatusCode::INTERNAL_SERVER_ERROR)
+        );
+        assert!(server.paths().iter().all(|p| p == "/sets/KTK/booster"));
+
+        let missing = mock::respond("404 Not Found", &[], "");
+        let server = mock::serve(vec![missing.clone(), missing]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let opened = open_packs_with(&client, "ZZZ", 2).await;
+        assert!(opened.packs.is_empty());
+        assert_eq!(opened.failed.len(), 2);
+        assert!(opened.failed.iter().all(|e| matches!(
+            e,
+            MTGCardError::WrappedAPI {
+                e: mtg_api::APIError::NotFound {}
+            }
+        )));
+        let none = open_packs_with(&client, "KTK", 0).await;
+        assert!(none.packs.is_empty() && none.failed.is_empty());
+    }
+
+    #[tokio::test]
+    async fn download_records_failed_page() {
+        let server = mock::serve(vec![
+            mock::json_page(r#"{"cards":[{"name":"A"},{"name":"B"}]}"#, 2, 5),
+            mock::json_page(r#"{"cards":[{"name":"C"},{"name":"D"}]}"#, 2, 5),
+            mock::respond("500 Internal Server Error", &[], ""),
+        ])
+        .await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let download = download_all_resilient_with(&client).await.unwrap();
+        let names: Vec<&str> = download
+            .cards_written
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, ["A", "B", "C", "D"]);
+
+        // Pages 2 and 3 are requested together, the failed one is whichever came last
+        let paths = server.paths();
+        assert_eq!(paths[0], "/cards?page=1&pageSize=100");
+        let failed = if paths[2].contains("page=2&") { 2 } else { 3 };
+        assert_eq!(download.failed_pages, [failed]);
+    }
+
+    #[tokio::test]
+    async fn printings_in_release_order() {
+        let printings = r#"{"cards":[
+            {"name":"Shock","set":"M19"},
+            {"name":"Shock","set":"PLST"},
+            {"name":"Shock","set":"STH"},
+            {"name":"Shock","set":"10E"}
+        ]}"#;
+        let sets = mock::json(
+            r#"{"sets":[
+            {"code":"10E","releaseDate":"2007-07-13"},
+            {"code":"STH","releaseDate":"1998-03-02"},
+            {"code":"M19","releaseDate":"2018-07-13"}
+        ]}"#,
+        );
+        let server = mock::serve(vec![mock::json_page(printings, 100, 4), sets]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let history = printing_history_with(&client, "Shock").await.unwrap();
+        let timeline: Vec<(&str, Option<&str>)> = history
+            .iter()
+            .map(|(c, date)| (c.set(), date.as_deref()))
+            .collect();
+        assert_eq!(
+            timeline,
+            [
+                ("STH", Some("1998-03-02")),
+                ("10E", Some("2007-07-13")),
+                ("M19", Some("2018-07-13")),
+                ("PLST", None)
+            ]
+        );
+        assert_eq!(server.paths()[1], "/sets");
+    }
+
+    #[tokio::test]
+    async fn merge_cards_of_sets() {
+        let sets = || mock::json(r#"{"sets":[{"code":"MH1"},{"code":"MH2"},{"code":"KTK"}]}"#);
+        let mh1 = || {
+            mock::json_page(
+                r#"{"cards":[{"name":"Force of Negation"},{"name":"Shock"}]}"#,
+                100,
+                2,
+            )
+        };
+        let mh2 = || mock::json_page(r#"{"cards":[{"name":"Shock"}]}"#, 100, 1);
+        let server = mock::serve(vec![sets(), mh1(), mh2(), sets(), mh1(), mh2(), sets()]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let pool = cards_in_sets_with(&client, &["mh1", "MH2", "MH1"], false)
+            .await
+            .unwrap();
+        let mut names: Vec<&str> = pool.cards.iter().map(|c| c.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["Force of Negation", "Shock", "Shock"]);
+
+        let mut paths = server.paths();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "/cards?set=MH1&page=1&pageSize=100",
+                "/cards?set=MH2&page=1&pageSize=100",
+                "/sets"
+            ]
+        );
+
+        // Deduplicating keeps one printing of the card reprinted across the sets
+        let unique = cards_in_sets_with(&client, &["MH1", "MH2"], true)
+            .await
+            .unwrap();
+        let mut names: Vec<&str> = unique.cards.iter().map(|c| c.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["Force of Negation", "Shock"]);
+
+        match cards_in_sets_with(&client, &["KTK", "zzz", "ZZ1"], false).await {
+            Err(MTGCardError::UnknownSets { codes }) => assert_eq!(codes, ["zzz", "ZZ1"]),
+            other => panic!("Expected UnknownSets, got {:?}", other),
+        }
+        assert_eq!(server.hits(), 7);
+    }
+
+    #[tokio::test]
+    async fn fetch_sets_released_since() {
+        let sets = || {
+            mock::json(
+                r#"{"sets":[
+                {"code":"OLD","releaseDate":"2023-01-01"},
+                {"code":"NEW","releaseDate":"2024-06-01"},
+                {"code":"NOW","releaseDate":"2024-03-01"}
+            ]}"#,
+            )
+        };
+        let page = |name: &str| {
+            let body = format!(r#"{{"cards":[{{"name":"{name}"}}]}}"#);
+            mock::json_page(&body, 100, 1)
+        };
+        let server = mock::serve(vec![sets(), page("First"), page("Second"), sets()]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let found = cards_since_with(&client, "2024-01-31").await.unwrap();
+        assert_eq!(found.cards.len(), 2);
+        let mut paths = server.paths();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "/cards?set=NEW&page=1&pageSize=100",
+                "/cards?set=NOW&page=1&pageSize=100",
+                "/sets"
+            ]
+        );
+
+        // Nothing newer is an empty result, not an error
+        let found = cards_since_with(&client, "2024-06-01").await.unwrap();
+        assert!(found.cards.is_empty());
+        assert_eq!(server.hits(), 4);
+
+        let invalid = [
+            "2024-1-31",
+            "2024-13-01",
+            "yesterday",
+            "2024-01-31T00:00",
+            "2024-02-31",
+            "2023-04-31",
+            "2023-02-29",
+            "1900-02-29",
+            "2024-06-00",
+        ];
+        for date in invalid {
+            match cards_since_with(&client, date).await {
+                Err(MTGCardError::InvalidDate { date: got }) => assert_eq!(got, date),
+                other => panic!("Expected InvalidDate, got {:?}", other),
+            }
+        }
+        assert_eq!(server.hits(), 4);
+
+        // Leap days exist in leap years, including those divisible by 400
+        for date in ["2024-02-29", "2000-02-29", "2023-12-31", "2023-04-30"] {
+            assert!(check_date(date).is_ok(), "{date} should be valid");
+        }
+    }
+
+    #[tokio::test]
+    async fn pool_of_format() {
+        let sets = mock::json(
+            r#"{"sets":[{"code":"UST","type":"funny"},{"code":"PZ1","onlineOnly":true}]}"#,
+        );
+        let legal = |name: &str, set: &str, legality: &str| {
+            format!(
+                r#"{{"name":"{name}","set":"{set}","legalities":[{{"format":"Pioneer","legality":"{legality}"}}]}}"#
+            )
+        };
+        let first = format!(
+            r#"{{"cards":[{},{},{}]}}"#,
+            legal("Fatal Push", "AER", "Legal"),
+            legal("Oko, Thief of Crowns", "ELD", "Banned"),
+            legal("Fatal Push", "2XM", "Legal"),
+        );
+        let second = format!(
+            r#"{{"cards":[{},{}]}}"#,
+            legal("Very Cryptic Command", "UST", "Legal"),
+            legal("Fatal Push", "PZ1", "Legal"),
+        );
+        let server = mock::serve(vec![
+            sets,
+            mock::json_page(&first, 3, 5),
+            mock::json_page(&second, 3, 5),
+        ])
+        .await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let pool = format_pool_with(&client, "pioneer").await.unwrap();
+        let printings: Vec<(&str, &str)> =
+            pool.iter().map(|c| (c.name.as_str(), c.set())).collect();
+        assert_eq!(printings, [("Fatal Push", "AER")]);
+        assert_eq!(
+            server.paths()[1..],
+            [
+                "/cards?gameFormat=pioneer&page=1&pageSize=100",
+                "/cards?gameFormat=pioneer&page=2&pageSize=100"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn card_colors() {
+        let bolt = &name_find("Lightning Bolt").await.unwrap().cards[0];
+        assert_eq!(bolt.colors(), ["Red"]);
+        assert_eq!(bolt.color_identity(), ["R"]);
+
+        // Colorless cards leave the fields out entirely
+        let colorless = serde_json::json!({"name": "Ornithopter", "manaCost": "{0}"});
+        let ornithopter = Card::from_json_value(&colorless).unwrap();
+        assert!(ornithopter.colors().is_empty());
+        assert!(ornithopter.color_identity().is_empty());
+    }
+
+    #[test]
+    fn creature_stats() {
+        let creature = serde_json::json!({
+            "name": "Goblin Guide", "type": "Creature — Goblin Scout", "power": "2", "toughness": "2"
+        });
+        let guide = Card::from_json_value(&creature).unwrap();
+        assert_eq!(guide.power(), "2");
+        assert_eq!(guide.toughness(), "2");
+        assert_eq!(guide.loyalty(), "");
+
+        let planeswalker = serde_json::json!({"name": "Narset Transcendent", "loyalty": "6"});
+        assert_eq!(Card::from_json_value(&planeswalker).unwrap().loyalty(), "6");
+
+        // Non-creatures leave the fields out entirely
+        let sorcery = serde_json::json!({"name": "Wrath of God", "type": "Sorcery"});
+        let wrath = Card::from_json_value(&sorcery).unwrap();
+        assert_eq!(wrath.power(), "");
+        assert_eq!(wrath.toughness(), "");
+    }
+
+    #[tokio::test]
+    async fn card_image_url() {
+        let url = "http://gatherer.wizards.com/Handlers/Image.ashx?multiverseid=386616&type=card";
+        let value = serde_json::json!({"name": "Narset, Enlightened Master", "imageUrl": url});
+        let narset = Card::from_json_value(&value).unwrap();
+        assert_eq!(narset.image_url(), url);
+
+        let promo = Card::from_json_value(&serde_json::json!({"name": "Promo"})).unwrap();
+        assert_eq!(promo.image_url(), "");
+        assert!(matches!(
+            promo.download_image().await,
+            Err(MTGCardError::NoImageError {})
+        ));
+    }
+
+    #[test]
+    fn card_multiverseid() {
+        let text =
+            serde_json::json!({"name": "Narset, Enlightened Master", "multiverseid": "386616"});
+        assert_eq!(
+            Card::from_json_value(&text).unwrap().multiverseid(),
+            Some(386616)
+        );
+
+        let number = serde_json::json!({"multiverseid": 386616});
+        assert_eq!(
+            Card::from_json_value(&number).unwrap().multiverseid(),
+            Some(386616)
+        );
+
+        let promo = serde_json::json!({"name": "Promo"});
+        assert_eq!(Card::from_json_value(&promo).unwrap().multiverseid(), None);
+
+        let invalid = serde_json::json!({"multiverseid": "abc"});
+        assert!(Card::from_json_value(&invalid).is_err());
+    }
+
+    #[tokio::test]
+    async fn find_by_multiverseid() {
+        let res = mtg_api::card_by_multiverseid(386616).await.unwrap();
+        let found = MultiCards::from_response(res).await.unwrap();
+        assert!(!found.cards.is_empty());
+        assert!(found.cards.iter().all(|c| c.multiverseid() == Some(386616)));
+        assert_eq!(found.cards[0].name, "Narset, Enlightened Master");
+    }
+
+    #[test]
+    fn card_legalities() {
+        let value = serde_json::json!({
+            "name": "Narset, Enlightened Master",
+            "legalities": [
+                {"format": "Commander", "legality": "Legal"},
+                {"format": "Khans of Tarkir Block", "legality": "Legal"}
+            ]
+        });
+        let narset = Card::from_json_value(&value).unwrap();
+        assert_eq!(narset.legalities().len(), 2);
+        assert_eq!(
+            narset.legalities()[0],
+            Legality {
+                format: "Commander".to_owned(),
+                legality: "Legal".to_owned()
+            }
+        );
+        assert!(Card::default().legalities().is_empty());
+    }
+
+    #[tokio::test]
+    async fn find_by_legality() {
+        let res = mtg_api::card_by_legality("Commander", "Legal")
+            .await
+            .unwrap();
+        let found = MultiCards::from_response(res).await.unwrap();
+        assert!(!found.cards.is_empty());
+        assert!(found.cards.iter().any(|c| c
+            .legalities()
+            .iter()
+            .any(|l| l.format == "Commander" && l.legality == "Legal")));
+    }
+
+    #[tokio::test]
+    async fn find_by_text() {
+        let res = mtg_api::card_by_text("first strike").await.unwrap();
+        let found = MultiCards::from_response(res).await.unwrap();
+        assert!(!found.cards.is_empty());
+        assert!(found
+            .cards
+            .iter()
+            .any(|c| c.text().to_lowercase().contains("first strike")));
+    }
+
+    #[tokio::test]
+    async fn find_by_flavor() {
+        let res = mtg_api::card_by_flavor("Tolarian Academy").await.unwrap();
+        let found = MultiCards::from_response(res).await.unwrap();
+        assert!(!found.cards.is_empty());
+        assert!(found
+            .cards
+            .iter()
+            .all(|c| c.flavor().contains("Tolarian Academy")));
+    }
+
+    #[tokio::test]
+    async fn find_by_german_name() {
+        let res = mtg_api::card_by_name_in_language("Blitzschlag", "German")
+            .await
+            .unwrap();
+        let found = MultiCards::from_response(res).await.unwrap();
+        assert!(found.cards.iter().any(|c| c.name == "Lightning Bolt"));
+    }
+
+    #[tokio::test]
+    async fn find_by_cmc_range() {
+        let res = mtg_api::card_by_cmc_range(None, Some(1.0)).await.unwrap();
+        let cheap = MultiCards::from_response(res).await.unwrap();
+        assert!(!cheap.cards.is_empty());
+        assert!(cheap.cards.iter().all(|c| c.cmc() <= 1.0));
+    }
+
+    #[tokio::test]
+    async fn find_by_cmc_between_bounds() {
+        let upto_two = r#"{"cards":[
+            {"name":"Ornithopter","cmc":0},{"name":"Bolt","cmc":1},
+            {"name":"Little Girl","cmc":0.5},{"name":"Half","cmc":1.5},{"name":"Shock","cmc":2}
+        ]}"#;
+        let two = r#"{"cards":[{"name":"Shock","cmc":2}]}"#;
+        let count = |total| mock::json_page(r#"{"cards":[{"name":"Any"}]}"#, 1, total);
+        let server = mock::serve(vec![
+            count(3),
+            count(5000),
+            mock::json_page(upto_two, 100, 5),
+            mock::json_page(two, 100, 1),
+        ])
+        .await;
+        let client = MtgClient::new().with_base_url(&server.url);
+
+        // Whole bounds still keep the fractional costs between them
+        let found = cmc_between_find_with(&client, Some(1.0), Some(2.0))
+            .await
+            .unwrap();
+        let names: Vec<&str> = found.cards.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Bolt", "Half", "Shock"]);
+
+        // Equal whole bounds take a single exact search
+        let exact = cmc_between_find_with(&client, Some(2.0), Some(2.0))
+            .await
+            .unwrap();
+        assert_eq!(exact.cards.len(), 1);
+        assert_eq!(
+            server.paths(),
+            vec![
+                "/cards?cmc=lte2&pageSize=1",
+                "/cards?cmc=gte1&pageSize=1",
+                "/cards?cmc=lte2&page=1&pageSize=100",
+                "/cards?cmc=2&page=1&pageSize=100"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn find_by_cmc_between_narrower_bound() {
+        let first = r#"{"cards":[{"name":"Half","cmc":0.5},{"name":"One","cmc":1}]}"#;
+        let second = r#"{"cards":[{"name":"Two","cmc":2}]}"#;
+        let count = |total| mock::json_page(r#"{"cards":[{"name":"Any"}]}"#, 1, total);
+        let server = mock::serve(vec![
+            count(5000),
+            count(3),
+            mock::json_page(first, 2, 3),
+            mock::json_page(second, 2, 3),
+        ])
+        .await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let found = cmc_between_find_with(&client, Some(0.5), Some(1.0))
+            .await
+            .unwrap();
+        let names: Vec<&str> = found.cards.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Half", "One"]);
+
+        // The lower bound matches fewer cards, so only its pages are fetched
+        assert_eq!(
+            server.paths(),
+            vec![
+                "/cards?cmc=lte1&pageSize=1",
+                "/cards?cmc=gte0.5&pageSize=1",
+                "/cards?cmc=gte0.5&page=1&pageSize=100",
+                "/cards?cmc=gte0.5&page=2&pageSize=100"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn find_by_cmc_range_each_cost() {
+        let two = r#"{"cards":[{"name":"Shock","id":"a"},{"name":"Split","id":"b"}]}"#;
+        let three = r#"{"cards":[{"name":"Split","id":"b"},{"name":"Flame","id":"c"}]}"#;
+        let server = mock::serve(vec![
+            mock::json_page(two, 100, 2),
+            mock::json_page(three, 100, 2),
+        ])
+        .await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let found = cmc_range_find(2, 3, client.query().colors(&["red"]))
+            .await
+            .unwrap();
+        let names: Vec<&str> = found.cards.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Shock", "Split", "Flame"]);
+        assert_eq!(
+            server.paths(),
+            vec![
+                "/cards?colors=red&cmc=2&page=1&pageSize=100",
+                "/cards?colors=red&cmc=3&page=1&pageSize=100"
+            ]
+        );
+
+        for (min, max) in [(3, 2), (0, MAX_CMC_RANGE)] {
+            match cmc_range_find(min, max, client.query()).await {
+                Err(MTGCardError::WrappedAPI {
+                    e: mtg_api::APIError::UnsupportedQuery { .. },
+                }) => (),
+                other => panic!("Expected UnsupportedQuery, got {:?}", other),
+            }
+        }
+        assert_eq!(server.hits(), 2);
+    }
+
+    #[tokio::test]
+    async fn find_all_query_pages() {
+        let server = mock::serve(vec![mock::json_page(r#"{"cards":[]}"#, 100, 0)]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let found = query_find_all(client.query().artist("Nobody"))
+            .await
+            .unwrap();
+        assert!(found.cards.is_empty());
+        assert_eq!(server.paths().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn find_cheap_cards_in_range() {
+        let cheap = r#"{"cards":[{"name":"Ornithopter","cmc":0},{"name":"Bolt","cmc":1},{"name":"Shock","cmc":2}]}"#;
+        let server = mock::serve(vec![mock::json_page(cheap, 100, 3)]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let found = cmc_between_find_with(&client, None, Some(1.0))
+            .await
+            .unwrap();
+        assert_eq!(found.cards.len(), 2);
+        assert!(found.cards.iter().all(|c| c.cmc() <= 1.0));
+        assert_eq!(server.paths(), vec!["/cards?cmc=lte1&page=1&pageSize=100"]);
+    }
+
+    #[tokio::test]
+    async fn count_distinct_names_through_rate_limit() {
+        let limited = mock::respond("429 Too Many Requests", &[("retry-after", "0")], "");
+        let server = mock::serve(vec![
+            mock::json(r#"{"cards":[{"name":"A","set":"X"},{"name":"B"},{"name":"A"}]}"#),
+            limited,
+            mock::json(r#"{"cards":[{"name":"B"},{"name":"C"}]}"#),
+            mock::json(r#"{"cards":[]}"#),
+        ])
+        .await;
+        let client = MtgClient::new()
+            .with_base_url(&server.url)
+            .with_max_retries(1);
+        assert_eq!(distinct_card_count_with(&client).await.unwrap(), 3);
+        assert_eq!(server.paths().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn find_by_artist() {
+        let res = mtg_api::card_by_artist("Rebecca Guay").await.unwrap();
+        let found = MultiCards::from_response(res).await.unwrap();
+        assert!(!found.cards.is_empty());
+        assert!(found
+            .cards
+            .iter()
+            .all(|c| c.artist().contains("Rebecca Guay")));
+    }
+
+    #[test]
+    fn card_type_lines() {
+        let value = serde_json::json!({
+            "name": "Narset, Enlightened Master",
+            "type": "Legendary Creature — Human Monk",
+            "supertypes": ["Legendary"],
+            "types": ["Creature"],
+            "subtypes": ["Human", "Monk"]
+        });
+        let narset = Card::from_json_value(&value).unwrap();
+        assert_eq!(narset.supertypes(), ["Legendary"]);
+        assert_eq!(narset.types(), ["Creature"]);
+        assert_eq!(narset.subtypes(), ["Human", "Monk"]);
+
+        let bolt = serde_json::json!({"name": "Lightning Bolt", "types": ["Instant"]});
+        let bolt = Card::from_json_value(&bolt).unwrap();
+        assert!(bolt.supertypes().is_empty());
+        assert!(bolt.subtypes().is_empty());
+    }
+
+    #[test]
+    fn card_layout() {
+        let bolt = serde_json::json!({"name": "Lightning Bolt", "layout": "normal"});
+        let bolt = Card::from_json_value(&bolt).unwrap();
+        assert_eq!(bolt.layout(), "normal");
+        assert!(!bolt.is_multifaced());
+
+        let fire = serde_json::json!({"name": "Fire", "layout": "split", "names": ["Fire", "Ice"]});
+        let fire = Card::from_json_value(&fire).unwrap();
+        assert_eq!(fire.layout(), "split");
+        assert!(fire.is_multifaced());
+    }
+
+    #[tokio::test]
+    async fn split_card_faces() {
+        let fire = name_find("Fire").await.unwrap();
+        let half = fire.cards.iter().find(|c| c.layout == "split").unwrap();
+        assert_eq!(half.faces(), ["Fire", "Ice"]);
+
+        // Cards with one face leave the field out entirely
+        let bolt = serde_json::json!({"name": "Lightning Bolt", "layout": "normal"});
+        assert!(Card::from_json_value(&bolt).unwrap().faces().is_empty());
+    }
+
+    #[test]
+    fn card_rulings() {
+        let value = serde_json::json!({
+            "name": "Narset, Enlightened Master",
+            "rulings": [{"date": "2014-09-20", "text": "The cards are exiled face up."}]
+        });
+        let narset = Card::from_json_value(&value).unwrap();
+        assert_eq!(
+            narset.rulings(),
+            [Ruling {
+                date: "2014-09-20".to_owned(),
+                text: "The cards are exiled face up.".to_owned()
+            }]
+        );
+    }
+
+    #[test]
+    fn card_foreign_names() {
+        let value = serde_json::json!({
+            "name": "Lightning Bolt",
+            "foreignNames": [
+                {"name": "Blitzschlag", "language": "German", "multiverseid": 148411},
+                {"name": "Foudre", "language": "French", "imageUrl": "http://example.com/foudre"}
+            ]
+        });
+        let bolt = Card::from_json_value(&value).unwrap();
+        assert_eq!(bolt.name_in_language("German"), Some("Blitzschlag"));
+        assert_eq!(bolt.name_in_language("french"), Some("Foudre"));
+        assert_eq!(bolt.name_in_language("Japanese"), None);
+        assert_eq!(bolt.foreign_names()[0].multiverseid, Some(148411));
+        assert_eq!(
+            bolt.foreign_names()[1].image_url,
+            "http://example.com/foudre"
+        );
+    }
+
+    #[test]
+    fn card_printing_details() {
+        let value = serde_json::json!({
+            "name": "Narset, Enlightened Master",
+            "set": "KTK",
+            "number": "190",
+            "releaseDate": "2014-09-26"
+        });
+        let narset = Card::from_json_value(&value).unwrap();
+        assert_eq!(narset.id(), "");
+        assert_eq!(narset.set(), "KTK");
+        assert_eq!(narset.number().parse::<u32>(), Ok(190));
+        assert_eq!(narset.release_date(), "2014-09-26");
+
+        let half = serde_json::json!({"name": "Who", "number": "12a"});
+        assert_eq!(Card::from_json_value(&half).unwrap().number(), "12a");
+    }
+
+    #[test]
+    fn card_original_wording() {
+        let value = serde_json::json!({
+            "name": "Mons's Goblin Raiders",
+            "type": "Creature — Goblin",
+            "text": "",
+            "originalType": "Summon — Goblins",
+            "originalText": ""
+        });
+        let raiders = Card::from_json_value(&value).unwrap();
+        assert_eq!(raiders.original_type(), "Summon — Goblins");
+        assert_ne!(raiders.original_type(), raiders.type_field);
+        assert_eq!(Card::default().original_text(), "");
+    }
+
+    #[tokio::test]
+    async fn errata_original_text() {
+        let bolts = name_find("Lightning Bolt").await.unwrap();
+        let alpha = bolts
+            .cards
+            .iter()
+            .find(|c| !c.original_text().is_empty())
+            .unwrap();
+        assert_ne!(alpha.original_text(), alpha.text());
+    }
+
+    #[test]
+    fn card_reserved_list() {
+        let value = serde_json::json!({"name": "Volcanic Island", "reserved": true});
+        assert!(Card::from_json_value(&value).unwrap().is_reserved());
+
+        let modern = serde_json::json!({"name": "Llanowar Elves"});
+        assert!(!Card::from_json_value(&modern).unwrap().is_reserved());
+    }
+
+    #[tokio::test]
+    async fn find_reserved_dual_land() {
+        let islands = name_find("Volcanic Island").await.unwrap();
+        assert!(islands.cards.iter().all(|c| c.is_reserved()));
+        let elves = name_find("Llanowar Elves").await.unwrap();
+        assert!(elves.cards.iter().all(|c| !c.is_reserved()));
+    }
+
+    #[test]
+    fn card_border_and_timeshifted() {
+        let value = serde_json::json!({"name": "Grizzly Bears", "border": "black"});
+        let bears = Card::from_json_value(&value).unwrap();
+        assert_eq!(bears.border(), "black");
+        assert!(!bears.is_timeshifted());
+
+        let value = serde_json::json!({
+            "name": "Dragonstorm",
+            "border": "white",
+            "timeshifted": true
+        });
+        let storm = Card::from_json_value(&value).unwrap();
+        assert_eq!(storm.border(), "white");
+        assert!(storm.is_timeshifted());
+    }
+
+    #[test]
+    fn card_vanguard_modifiers() {
+        let value = serde_json::json!({
+            "name": "Sisay",
+            "type": "Vanguard",
+            "hand": 0,
+            "life": "+4"
+        });
+        let sisay = Card::from_json_value(&value).unwrap();
+        assert_eq!(sisay.hand(), Some(0));
+        assert_eq!(sisay.life(), Some(4));
+
+        let value = serde_json::json!({"name": "Eladamri", "hand": "-1", "life": 15});
+        let eladamri = Card::from_json_value(&value).unwrap();
+        assert_eq!((eladamri.hand(), eladamri.life()), (Some(-1), Some(15)));
+
+        let vanilla = Card::from_json_value(&serde_json::json!({"name": "Grizzly Bears"}));
+        let vanilla = vanilla.unwrap();
+        assert_eq!((vanilla.hand(), vanilla.life()), (None, None));
+    }
+
+    #[test]
+    fn card_starter_flag() {
+        let value = serde_json::json!({"name": "Wood Elves", "starter": true});
+        assert!(Card::from_json_value(&value).unwrap().is_starter());
+
+        let value = serde_json::json!({"name": "Wood Elves", "manaCost": "{2}{G}", "cmc": 3});
+        let elves = Card::from_json_value(&value).unwrap();
+        assert!(!elves.is_starter());
+        assert_eq!(elves.cmc(), 3.0);
+    }
+
+    #[test]
+    fn card_watermark() {
+        let value = serde_json::json!({"name": "Azorius Charm", "watermark": "Azorius"});
+        assert_eq!(
+            Card::from_json_value(&value).unwrap().watermark(),
+            "Azorius"
+        );
+
+        let vanilla = serde_json::json!({"name": "Grizzly Bears"});
+        assert_eq!(Card::from_json_value(&vanilla).unwrap().watermark(), "");
+    }
+
+    #[tokio::test]
+    async fn guild_watermark() {
+        let charms = name_find("Azorius Charm").await.unwrap();
+        assert!(charms.cards.iter().any(|c| c.watermark() == "Azorius"));
+    }
+
+    #[test]
+    fn many_printings() {
+        let codes: Vec<String> = (0..2000).map(|n| format!("S{n}")).collect();
+        let value = serde_json::json!({"name": "Island", "printings": codes});
+        let island = Card::from_json_value(&value).unwrap();
+        assert_eq!(island.printings().len(), 2000);
+        assert_eq!(island.printings()[1999], "S1999");
+    }
+
+    #[test]
+    fn card_from_json() {
+        let json = r#"{"name": "Lightning Bolt", "manaCost": "{R}", "cmc": 1, "type": "Instant"}"#;
+        let bolt = Card::from_json(json).unwrap();
+        assert_eq!(bolt.name, "Lightning Bolt");
+        assert_eq!(bolt.type_field, "Instant");
+        assert_eq!(bolt.cmc(), 1.0);
+        assert!(Card::from_json("{").is_err());
+
+        let cards = MultiCards::from_json(&format!(r#"{{"cards":[{json},{json}]}}"#)).unwrap();
+        assert_eq!(cards.cards, vec![bolt.clone(), bolt]);
+        assert!(matches!(
+            MultiCards::from_json(r#"{"card":{}}"#),
+            Err(MTGCardError::WrappedSerde { .. })
+        ));
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let json = r#"{"cards":[{
+            "name": "Narset, Enlightened Master", "manaCost": "{3}{U}{R}{W}", "cmc": 6,
+            "multiverseid": "386616", "legalities": [{"format": "Commander", "legality": "Legal"}]
+        }]}"#;
+        let cards = MultiCards::from_json(json).unwrap();
+        assert_eq!(
+            MultiCards::from_json(&cards.to_json().unwrap()).unwrap(),
+            cards
+        );
+
+        let pretty = cards.to_pretty_json().unwrap();
+        assert!(pretty.contains("\n  \"cards\": ["));
+        assert_eq!(MultiCards::from_json(&pretty).unwrap(), cards);
+    }
+
+    #[tokio::test]
+    async fn fetched_json_round_trip() {
+        let fetched = name_find("Lightning Bolt").await.unwrap();
+        let cached = fetched.to_pretty_json().unwrap();
+        assert_eq!(MultiCards::from_json(&cached).unwrap(), fetched);
+    }
+
+    #[test]
+    fn card_from_json_value() {
+        let value = serde_json::json!({
+            "card": {"name": "Lightning Bolt", "manaCost": "{R}", "cmc": 1.0}
+        });
+        let card = Card::from_json_value(&value["card"]).unwrap();
+        assert_eq!(card.name, "Lightning Bolt");
+        assert_eq!(card.mana_cost, "{R}");
+        assert_eq!(card.cmc, 1.0);
+
+        let invalid = serde_json::json!({"name": 42});
+        let err = Card::from_json_value(&invalid).unwrap_err();
+        assert!(matches!(err, MTGCardError::WrappedSerde { .. }));
+    }
+
+    #[test]
+    fn search_stats() {
+        let card = |name: &str, mana_cost: &str, cmc: f32, rarity: &str| Card {
+            name: name.to_string(),
+            mana_cost: mana_cost.to_string(),
+            cmc,
+            rarity: rarity.to_string(),
+            type_field: "Instant".to_string(),
+            ..Default::default()
+        };
+        let mut island = card("Island", "", 0.0, "Common");
+        island.type_field = "Basic Land — Island".to_string();
+        let results = MultiCards {
+            cards: vec![
+                card("Lightning Bolt", "{R}", 1.0, "Common"),
+                card("Lightning Bolt", "{R}", 1.0, "Uncommon"),
+                card("Fire // Ice", "{1}{R}", 2.0, "Uncommon"),
+                card("Narset, Enlightened Master", "{3}{U}{R}{W}", 6.0, "Mythic"),
+                island,
+            ],
+        };
+
+        let stats = results.stats();
+        assert_eq!(stats.total, 5);
+        assert_eq!(stats.distinct_names, 4);
+        assert_eq!(
+            stats.colors,
+            BTreeMap::from([(Color::White, 1), (Color::Blue, 1), (Color::Red, 4)])
+        );
+        assert_eq!(stats.rarities["Common"], 2);
+        assert_eq!(stats.rarities["Uncommon"], 2);
+        assert_eq!(stats.rarities["Mythic"], 1);
+        assert_eq!(stats.average_mana_value, 2.5);
+
+        assert_eq!(MultiCards { cards: vec![] }.stats(), SearchStats::default());
+    }
+
+    #[test]
+    fn keep_latest_printings() {
+        let printing = |name: &str, set_name: &str| Card {
+            name: name.to_string(),
+            set_name: set_name.to_string(),
+            ..Default::default()
+        };
+        let cards = vec![
+            printing("Narset, Enlightened Master", "Khans of Tarkir"),
+            printing("Narset, Parter of Veils", "War of the Spark"),
+            printing("Narset, Enlightened Master", "Commander Legends"),
+        ];
+        let unique = latest_printings(cards);
+        assert_eq!(unique.len(), 2);
+        assert_eq!(unique[0].name, "Narset, Parter of Veils");
+        assert_eq!(unique[1].set_name, "Commander Legends");
+    }
+
+    #[tokio::test]
+    async fn find_card_name_unique() {
+        let all = name_find("Lightning Bolt").await.unwrap();
+        let unique = name_find_unique("Lightning Bolt").await.unwrap();
+        assert!(all.cards.len() > unique.cards.len());
+        let history = printing_history("Lightning Bolt").await.unwrap();
+        let (latest, _) = history
+            .iter()
+            .rev()
+            .find(|(_, date)| date.is_some())
+            .unwrap();
+        assert_eq!(unique.cards.len(), 1);
+        assert_eq!(&unique.cards[0], latest);
+    }
+
+    #[tokio::test]
+    async fn find_card_name_unique_by_release() {
+        // Listed newest first, with a promo from a set that has no date
+        let printings = r#"{"cards":[
+            {"name":"Lightning Bolt","set":"2XM"},
+            {"name":"Lightning Bolt","set":"PRM"},
+            {"name":"Lightning Bolt","set":"LEA"}
+        ]}"#;
+        let sets = mock::json(
+            r#"{"sets":[{"code":"LEA","releaseDate":"1993-08-05"},{"code":"2XM","releaseDate":"2020-08-07"}]}"#,
+        );
+        let server = mock::serve(vec![mock::json_page(printings, 100, 3), sets]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let unique = name_find_unique_with(&client, "Lightning Bolt")
+            .await
+            .unwrap();
+        assert_eq!(unique.cards.len(), 1);
+        assert_eq!(unique.cards[0].set(), "2XM");
+    }
+
+    #[test]
+    fn power_sorting() {
+        let creature = |power: &str| Card {
+            power: power.to_string(),
+            ..Default::default()
+        };
+        assert_eq!(creature("3").power_sort_key(), 3.0);
+        assert_eq!(creature("-1").power_sort_key(), -1.0);
+        assert_eq!(creature("1+*").power_sort_key(), 1.5);
+        assert_eq!(creature("*+1").power_sort_key(), 1.5);
+        assert_eq!(creature("*").power_sort_key(), 1000.0);
+        assert_eq!(creature("").power_sort_key(), f32::MIN);
+
+        let mut creatures = [creature("*"), creature("2"), creature("1+*"), creature("1")];
+        creatures.sort_by(|a, b| a.power_sort_key().total_cmp(&b.power_sort_key()));
+        let powers: Vec<&str> = creatures.iter().map(|c| c.power.as_str()).collect();
+        assert_eq!(powers, vec!["1", "1+*", "2", "*"]);
+    }
+
+    #[tokio::test]
+    async fn find_any_or_all_types() {
+        let instants = r#"{"cards":[{"name":"Bolt","id":"a"},{"name":"Shock","id":"b"}]}"#;
+        let more_instants = r#"{"cards":[{"name":"Tribal","id":"c"}]}"#;
+        let sorceries = r#"{"cards":[{"name":"Tribal","id":"c"},{"name":"Rift","id":"d"}]}"#;
+        let server = mock::serve(vec![
+            mock::json_page(instants, 2, 3),
+            mock::json_page(more_instants, 2, 3),
+            mock::json_page(sorceries, 100, 2),
+            mock::json_page(r#"{"cards":[{"name":"Ornithopter","id":"e"}]}"#, 100, 1),
+            mock::json_page(r#"{"cards":[]}"#, 100, 0),
+        ])
+        .await;
+        let client = MtgClient::new().with_base_url(&server.url);
+
+        // Every page of each type is fetched and cards of both types kept once
+        let any = types_find_with(&client, &["Instant", "Sorcery"], true)
+            .await
+            .unwrap();
+        let names: Vec<&str> = any.cards.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["Bolt", "Shock", "Tribal", "Rift"]);
+
+        let all = types_find_with(&client, &["Artifact", "Creature"], false)
+            .await
+            .unwrap();
+        assert_eq!(all.cards.len(), 1);
+
+        let none = types_find_with(&client, &["Notatype"], true).await;
+        assert!(matches!(none, Err(MTGCardError::NoCardError {})));
+        assert_eq!(
+            server.paths(),
+            vec![
+                "/cards?types=Instant&page=1&pageSize=100",
+                "/cards?types=Instant&page=2&pageSize=100",
+                "/cards?types=Sorcery&page=1&pageSize=100",
+                "/cards?types=Artifact%2CCreature&page=1&pageSize=100",
+                "/cards?types=Notatype&page=1&pageSize=100"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn allow_empty_results() {
+        let res = mtg_api::card_by_types(&["Notatype"]).await.unwrap();
+        let empty = MultiCards::from_response_allow_empty(res).await.unwrap();
+        assert!(empty.cards.is_empty());
+
+        let res = mtg_api::card_exact_name_info("Narset, Enlightened Master")
+            .await
+            .unwrap();
+        let found = MultiCards::from_response_allow_empty(res).await.unwrap();
+        assert!(!found.cards.is_empty());
+    }
+
+    #[test]
+    fn deck_line() {
+        let bolt = Card {
+            name: "Lightning Bolt".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(bolt.to_deck_line(4), "4 Lightning Bolt");
+        assert_eq!(bolt.to_deck_line(1), "1 Lightning Bolt");
+        assert_eq!(bolt.to_deck_line(0), "");
+    }
+
+    #[test]
+    fn pretty_mana_cost() {
+        let narset = Card {
+            mana_cost: "{3}{U}{R}{W}".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(narset.pretty_mana(), "③🔵🔴⚪");
+        assert_eq!(Card::default().pretty_mana(), "");
+    }
+
+    #[test]
+    fn mana_cost_cmc_agree() {
+        let card = |mana_cost: &str, cmc: f32| Card {
+            mana_cost: mana_cost.to_string(),
+            cmc,
+            ..Default::default()
+        };
+        assert!(card("{3}{U}{R}{W}", 6.0).mana_cost_matches_cmc());
+        assert!(card("{X}{R}{R}", 2.0).mana_cost_matches_cmc());
+        assert!(card("", 0.0).mana_cost_matches_cmc());
+        assert!(!card("{3}{U}{R}{W}", 5.0).mana_cost_matches_cmc());
+    }
+
+    #[test]
+    fn mana_curve_chart() {
+        let card = |type_field: &str, cmc: f32| Card {
+            type_field: type_field.to_string(),
+            cmc,
+            ..Default::default()
+        };
+        let deck = MultiCards {
+            cards: vec![
+                card("Instant", 1.0),
+                card("Creature", 1.0),
+                card("Creature", 3.0),
+                card("Basic Land — Mountain", 0.0),
+                card("Creature", 0.5),
+            ],
+        };
+        assert_eq!(deck.mana_curve(), BTreeMap::from([(0, 1), (1, 2), (3, 1)]));
+
+        let chart = deck.mana_curve_chart();
+        let rows: Vec<&str> = chart.lines().collect();
+        assert_eq!(rows.len(), 4);
+        assert!(rows.iter().all(|r| r.len() == 50));
+        assert_eq!(&rows[0][..5], "0 |##");
+        assert_eq!(&rows[2][..4], "2 | ");
+        assert_eq!(rows[1].matches('#').count(), 45);
+        assert!(rows[1].ends_with(" 2"));
+
+        let empty = MultiCards { cards: vec![] };
+        assert_eq!(empty.mana_curve_chart(), "");
+    }
+
+    #[test]
+    fn uncastable_spells() {
+        let spell = |name: &str, cost: &str| Card {
+            name: name.to_string(),
+            mana_cost: cost.to_string(),
+            ..Default::default()
+        };
+        let spells = vec![
+            spell("Narset, Enlightened Master", "{3}{U}{R}{W}"),
+            spell("Lightning Bolt", "{R}"),
+            spell("Azorius Guildmage", "{W/U}{W/U}"),
+            spell("Ornithopter", "{0}"),
+            spell("Forest", ""),
+        ];
+
+        let blocked = uncastable_cards(&[Color::Red, Color::White], &spells);
+        let names: Vec<&str> = blocked.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["Narset, Enlightened Master"]);
+
+        let blocked = uncastable_cards(&[Color::Green], &spells);
+        assert_eq!(blocked.len(), 3);
+        assert!(uncastable_cards(&[], &spells[3..]).is_empty());
+    }
+
     #[test]
     fn convert_serde_error() {
         let serde_err = serde_json::Error::custom("Test");
         let mtg_err: MTGCardError = serde_err.into();
         assert_eq!(mtg_err.to_string(), "Wrapped serde Error: Test");
     }
+
+    #[test]
+    fn error_user_message() {
+        let none = MTGCardError::NoCardError {};
+        assert_eq!(
+            none.user_message(),
+            "No card matched; check spelling or try a partial search"
+        );
+
+        let api: MTGCardError = mtg_api::APIError::WrappedReqwest {
+            e: "dns".to_owned(),
+        }
+        .into();
+        assert!(api
+            .user_message()
+            .starts_with("The request to the MTG API failed"));
+    }
 }
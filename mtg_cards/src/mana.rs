@@ -0,0 +1,350 @@
+#![deny(missing_docs)]
+use std::fmt;
+
+/// One of the five colors of Magic
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Color {
+    /// `{W}`
+    White,
+    /// `{U}`
+    Blue,
+    /// `{B}`
+    Black,
+    /// `{R}`
+    Red,
+    /// `{G}`
+    Green,
+}
+
+impl Color {
+    /// Convert a mana symbol letter such as `U` into its [`Color`]
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "W" => Some(Color::White),
+            "U" => Some(Color::Blue),
+            "B" => Some(Color::Black),
+            "R" => Some(Color::Red),
+            "G" => Some(Color::Green),
+            _ => None,
+        }
+    }
+
+    /// A colored circle standing in for the color, e.g. 🔵 for [`Color::Blue`]
+    pub fn circle(&self) -> &'static str {
+        match self {
+            Color::White => "⚪",
+            Color::Blue => "🔵",
+            Color::Black => "⚫",
+            Color::Red => "🔴",
+            Color::Green => "🟢",
+        }
+    }
+
+    /// The letter used for the color in mana symbols, e.g. `U` for [`Color::Blue`]
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Color::White => "W",
+            Color::Blue => "U",
+            Color::Black => "B",
+            Color::Red => "R",
+            Color::Green => "G",
+        }
+    }
+}
+
+/// A single symbol within a mana cost, e.g. `{3}` or `{W/U}`
+#[derive(Clone, Debug, PartialEq)]
+pub enum ManaSymbol {
+    /// A fixed amount of generic mana such as `{3}`
+    Generic(u32),
+    /// A variable amount of generic mana such as `{X}`
+    Variable(char),
+    /// A single colored mana such as `{U}`
+    Colored(Color),
+    /// Specifically colorless mana `{C}`
+    Colorless,
+    /// Snow mana `{S}`
+    Snow,
+    /// Either of two colors such as `{W/U}`
+    Hybrid(Color, Color),
+    /// Either two generic or one colored mana such as `{2/W}`
+    TwoHybrid(Color),
+    /// A colored mana which can instead be paid with 2 life such as `{W/P}`
+    Phyrexian(Color),
+    /// Either of two colors, or instead 2 life, such as `{G/U/P}`
+    PhyrexianHybrid(Color, Color),
+    /// Any symbol not otherwise understood, kept as its raw text
+    Other(String),
+}
+
+impl ManaSymbol {
+    /// Parse the inside of a single `{...}` group
+    pub(crate) fn parse(inner: &str) -> Self {
+        if let Ok(n) = inner.parse() {
+            return ManaSymbol::Generic(n);
+        }
+        if let Some(c) = Color::from_symbol(inner) {
+            return ManaSymbol::Colored(c);
+        }
+        let parts: Vec<&str> = inner.split('/').collect();
+        match parts[..] {
+            ["X"] | ["Y"] | ["Z"] => ManaSymbol::Variable(inner.chars().next().unwrap_or('X')),
+            ["C"] => ManaSymbol::Colorless,
+            ["S"] => ManaSymbol::Snow,
+            ["2", c] => match Color::from_symbol(c) {
+                Some(c) => ManaSymbol::TwoHybrid(c),
+                None => ManaSymbol::Other(inner.to_owned()),
+            },
+            [c, "P"] => match Color::from_symbol(c) {
+                Some(c) => ManaSymbol::Phyrexian(c),
+                None => ManaSymbol::Other(inner.to_owned()),
+            },
+            [a, b, "P"] => match (Color::from_symbol(a), Color::from_symbol(b)) {
+                (Some(a), Some(b)) => ManaSymbol::PhyrexianHybrid(a, b),
+                _ => ManaSymbol::Other(inner.to_owned()),
+            },
+            [a, b] => match (Color::from_symbol(a), Color::from_symbol(b)) {
+                (Some(a), Some(b)) => ManaSymbol::Hybrid(a, b),
+                _ => ManaSymbol::Other(inner.to_owned()),
+            },
+            _ => ManaSymbol::Other(inner.to_owned()),
+        }
+    }
+
+    /// A compact rendering of the symbol for terminals, e.g. `③` for `{3}` or `🔵` for `{U}`
+    ///
+    /// Colors become colored circles. Generic mana from 0 to 20 becomes a circled number,
+    /// anything larger is written in brackets. Hybrid symbols show both halves, e.g.
+    /// `(⚪/🔵)`, and phyrexian symbols pair their color with `Φ`.
+    pub fn pretty(&self) -> String {
+        match self {
+            ManaSymbol::Generic(0) => "⓪".to_owned(),
+            ManaSymbol::Generic(n @ 1..=20) => char::from_u32(0x245F + n)
+                .map(String::from)
+                .unwrap_or_else(|| format!("({})", n)),
+            ManaSymbol::Generic(n) => format!("({})", n),
+            ManaSymbol::Variable(v) => v.to_string(),
+            ManaSymbol::Colored(c) => c.circle().to_owned(),
+            ManaSymbol::Colorless => "◇".to_owned(),
+            ManaSymbol::Snow => "❄".to_owned(),
+            ManaSymbol::Hybrid(a, b) => format!("({}/{})", a.circle(), b.circle()),
+            ManaSymbol::TwoHybrid(c) => format!("(②/{})", c.circle()),
+            ManaSymbol::Phyrexian(c) => format!("({}/Φ)", c.circle()),
+            ManaSymbol::PhyrexianHybrid(a, b) => format!("({}/{}/Φ)", a.circle(), b.circle()),
+            ManaSymbol::Other(_) => self.to_string(),
+        }
+    }
+
+    /// Contribution of this symbol to the mana value of a cost.
+    ///
+    /// Variable symbols such as `{X}` count as 0, half mana such as `{HW}` as 0.5 and
+    /// two-hybrid symbols as 2.
+    pub fn mana_value(&self) -> f32 {
+        match self {
+            ManaSymbol::Generic(n) => *n as f32,
+            ManaSymbol::Variable(_) => 0.0,
+            ManaSymbol::TwoHybrid(_) => 2.0,
+            ManaSymbol::Other(s) if s.starts_with('H') => 0.5,
+            ManaSymbol::Other(_) => 0.0,
+            _ => 1.0,
+        }
+    }
+
+    /// Whether this symbol can be paid for using only the given colors.
+    ///
+    /// Generic, variable, colorless and snow symbols never demand a color, a two-hybrid
+    /// can always be paid with generic mana and a phyrexian symbol, hybrid or not, with life.
+    pub fn payable_with(&self, available: &[Color]) -> bool {
+        match self {
+            ManaSymbol::Colored(c) => available.contains(c),
+            ManaSymbol::Hybrid(a, b) => available.contains(a) || available.contains(b),
+            _ => true,
+        }
+    }
+}
+
+/// Written as it appears in a mana cost, e.g. `{W/U}`
+impl fmt::Display for ManaSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ManaSymbol::Generic(n) => write!(f, "{{{}}}", n),
+            ManaSymbol::Variable(v) => write!(f, "{{{}}}", v),
+            ManaSymbol::Colored(c) => write!(f, "{{{}}}", c.symbol()),
+            ManaSymbol::Colorless => write!(f, "{{C}}"),
+            ManaSymbol::Snow => write!(f, "{{S}}"),
+            ManaSymbol::Hybrid(a, b) => write!(f, "{{{}/{}}}", a.symbol(), b.symbol()),
+            ManaSymbol::TwoHybrid(c) => write!(f, "{{2/{}}}", c.symbol()),
+            ManaSymbol::Phyrexian(c) => write!(f, "{{{}/P}}", c.symbol()),
+            ManaSymbol::PhyrexianHybrid(a, b) => {
+                write!(f, "{{{}/{}/P}}", a.symbol(), b.symbol())
+            }
+            ManaSymbol::Other(s) => write!(f, "{{{}}}", s),
+        }
+    }
+}
+
+/// A mana cost such as `{3}{U}{R}{W}` broken into its [`ManaSymbol`]s
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ManaCost {
+    symbols: Vec<ManaSymbol>,
+}
+
+impl ManaCost {
+    /// Parse a mana cost string, any text outside of `{...}` groups is ignored
+    pub fn parse(cost: &str) -> Self {
+        let symbols = cost
+            .split('{')
+            .skip(1)
+            .filter_map(|group| group.split_once('}'))
+            .map(|(inner, _)| ManaSymbol::parse(inner))
+            .collect();
+        ManaCost { symbols }
+    }
+
+    /// The individual symbols in the order they were written
+    pub fn symbols(&self) -> &[ManaSymbol] {
+        &self.symbols
+    }
+
+    /// Every symbol rendered with [`ManaSymbol::pretty`], e.g. `③🔵🔴⚪` for `{3}{U}{R}{W}`
+    pub fn pretty(&self) -> String {
+        self.symbols.iter().map(ManaSymbol::pretty).collect()
+    }
+
+    /// Total mana value of the cost, see [`ManaSymbol::mana_value`]
+    pub fn mana_value(&self) -> f32 {
+        self.symbols.iter().map(ManaSymbol::mana_value).sum()
+    }
+
+    /// Every color that appears in the cost, in WUBRG order
+    pub fn colors(&self) -> Vec<Color> {
+        let mut colors: Vec<Color> = Vec::new();
+        for symbol in &self.symbols {
+            match symbol {
+                ManaSymbol::Colored(c) | ManaSymbol::TwoHybrid(c) | ManaSymbol::Phyrexian(c) => {
+                    colors.push(*c)
+                }
+                ManaSymbol::Hybrid(a, b) | ManaSymbol::PhyrexianHybrid(a, b) => {
+                    colors.extend([*a, *b])
+                }
+                _ => (),
+            }
+        }
+        colors.sort();
+        colors.dedup();
+        colors
+    }
+
+    /// Whether every symbol of the cost can be paid for using only the given colors
+    pub fn castable_with(&self, available: &[Color]) -> bool {
+        self.symbols.iter().all(|s| s.payable_with(available))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_symbols() {
+        let cost = ManaCost::parse("{3}{U}{R}{W}");
+        assert_eq!(
+            cost.symbols(),
+            &[
+                ManaSymbol::Generic(3),
+                ManaSymbol::Colored(Color::Blue),
+                ManaSymbol::Colored(Color::Red),
+                ManaSymbol::Colored(Color::White),
+            ]
+        );
+
+        let cost = ManaCost::parse("{X}{W/U}{2/B}{G/P}{C}{S}{HW}{G/U/P}");
+        assert_eq!(
+            cost.symbols(),
+            &[
+                ManaSymbol::Variable('X'),
+                ManaSymbol::Hybrid(Color::White, Color::Blue),
+                ManaSymbol::TwoHybrid(Color::Black),
+                ManaSymbol::Phyrexian(Color::Green),
+                ManaSymbol::Colorless,
+                ManaSymbol::Snow,
+                ManaSymbol::Other("HW".to_owned()),
+                ManaSymbol::PhyrexianHybrid(Color::Green, Color::Blue),
+            ]
+        );
+
+        assert_eq!(
+            ManaCost::parse("{W/U/P}").symbols(),
+            &[ManaSymbol::PhyrexianHybrid(Color::White, Color::Blue)]
+        );
+        assert_eq!(
+            ManaCost::parse("{W/Q/P}").symbols(),
+            &[ManaSymbol::Other("W/Q/P".to_owned())]
+        );
+        assert!(ManaCost::parse("").symbols().is_empty());
+    }
+
+    #[test]
+    fn display_symbols() {
+        let cost = "{X}{2}{W/U}{2/B}{G/P}{C}{S}{HR}{R}{G/U/P}";
+        let shown: String = ManaCost::parse(cost)
+            .symbols()
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(shown, cost);
+    }
+
+    #[test]
+    fn pretty_symbols() {
+        assert_eq!(ManaCost::parse("{3}{U}{R}{W}").pretty(), "③🔵🔴⚪");
+        assert_eq!(ManaCost::parse("{0}{20}{21}{X}").pretty(), "⓪⑳(21)X");
+        assert_eq!(
+            ManaCost::parse("{W/U}{2/B}{G/P}{G/U/P}").pretty(),
+            "(⚪/🔵)(②/⚫)(🟢/Φ)(🟢/🔵/Φ)"
+        );
+        assert_eq!(ManaCost::parse("{C}{S}{HR}").pretty(), "◇❄{HR}");
+    }
+
+    #[test]
+    fn total_mana_value() {
+        assert_eq!(ManaCost::parse("{3}{U}{R}{W}").mana_value(), 6.0);
+        assert_eq!(ManaCost::parse("{X}{X}{R}").mana_value(), 1.0);
+        assert_eq!(ManaCost::parse("{2/W}{G/U}{B/P}{C}").mana_value(), 5.0);
+        assert_eq!(ManaCost::parse("{HR}").mana_value(), 0.5);
+        assert_eq!(ManaCost::parse("{1}{G/U/P}").mana_value(), 2.0);
+        assert_eq!(ManaCost::parse("").mana_value(), 0.0);
+    }
+
+    #[test]
+    fn cost_colors() {
+        assert_eq!(
+            ManaCost::parse("{3}{U}{R}{W}").colors(),
+            vec![Color::White, Color::Blue, Color::Red]
+        );
+        assert_eq!(
+            ManaCost::parse("{G/U}{2/B}{G}").colors(),
+            vec![Color::Blue, Color::Black, Color::Green]
+        );
+        assert_eq!(
+            ManaCost::parse("{W/U/P}").colors(),
+            vec![Color::White, Color::Blue]
+        );
+        assert!(ManaCost::parse("{4}{C}").colors().is_empty());
+    }
+
+    #[test]
+    fn castable_colors() {
+        let narset = ManaCost::parse("{3}{U}{R}{W}");
+        assert!(narset.castable_with(&[Color::White, Color::Blue, Color::Red]));
+        assert!(!narset.castable_with(&[Color::White, Color::Blue]));
+
+        let hybrid = ManaCost::parse("{1}{W/U}");
+        assert!(hybrid.castable_with(&[Color::Blue]));
+        assert!(hybrid.castable_with(&[Color::White]));
+        assert!(!hybrid.castable_with(&[Color::Green]));
+
+        let generic = ManaCost::parse("{4}{2/R}{B/P}");
+        assert!(generic.castable_with(&[]));
+    }
+}
@@ -0,0 +1,123 @@
+#![deny(missing_docs)]
+use mtg_api::CardQuery;
+
+use crate::{MTGCardError, MultiCards};
+
+/// Steps through the pages of a search one request at a time
+///
+/// ```no_run
+/// # async fn example() -> Result<(), mtg_cards::MTGCardError> {
+/// let query = mtg_api::CardQuery::new().artist("Rebecca Guay");
+/// let mut pages = mtg_cards::CardPaginator::with_query(query);
+/// while let Some(page) = pages.next_page().await? {
+///     println!("{}", page.cards.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct CardPaginator<'a> {
+    query: CardQuery<'a>,
+    /// The next page to fetch
+    page: u32,
+    /// Set once a page has come back empty
+    exhausted: bool,
+}
+
+impl Default for CardPaginator<'static> {
+    fn default() -> Self {
+        CardPaginator::with_query(CardQuery::new())
+    }
+}
+
+impl CardPaginator<'static> {
+    /// Step through every card in the API
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<'a> CardPaginator<'a> {
+    /// Step through the results of a search, any page set on the query is replaced
+    pub fn with_query(query: CardQuery<'a>) -> Self {
+        CardPaginator {
+            query,
+            page: 1,
+            exhausted: false,
+        }
+    }
+
+    /// Number of the page the next call to [`CardPaginator::next_page`] fetches
+    pub fn page(&self) -> u32 {
+        self.page
+    }
+
+    /// Fetch the next page, or `None` once a page comes back empty
+    ///
+    /// After an empty page no more requests are made.
+    pub async fn next_page(&mut self) -> Result<Option<MultiCards>, MTGCardError> {
+        if self.exhausted {
+            return Ok(None);
+        }
+        let res = self.query.clone().page(self.page).send().await?;
+        match MultiCards::from_response(res).await {
+            Ok(cards) => {
+                self.page += 1;
+                Ok(Some(cards))
+            }
+            Err(MTGCardError::NoCardError {}) => {
+                self.exhausted = true;
+                Ok(None)
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mtg_api::mock;
+    use mtg_api::MtgClient;
+
+    #[tokio::test]
+    async fn step_through_pages() {
+        let server = mock::serve(vec![
+            mock::json(r#"{"cards":[{"name":"First"}]}"#),
+            mock::json(r#"{"cards":[{"name":"Second"}]}"#),
+            mock::json(r#"{"cards":[]}"#),
+        ])
+        .await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let mut pages = CardPaginator::with_query(client.query().set("KTK"));
+
+        let first = pages.next_page().await.unwrap().unwrap();
+        assert_eq!(first.cards[0].name, "First");
+        let second = pages.next_page().await.unwrap().unwrap();
+        assert_eq!(second.cards[0].name, "Second");
+        assert!(pages.next_page().await.unwrap().is_none());
+        assert_eq!(pages.page(), 3);
+
+        // Once exhausted no more requests are sent
+        assert!(pages.next_page().await.unwrap().is_none());
+        assert_eq!(
+            server.paths(),
+            vec![
+                "/cards?set=KTK&page=1",
+                "/cards?set=KTK&page=2",
+                "/cards?set=KTK&page=3"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn page_artist_search() {
+        let query = CardQuery::new().artist("Rebecca Guay").page_size(10);
+        let mut pages = CardPaginator::with_query(query);
+        let first = pages.next_page().await.unwrap().unwrap();
+        let second = pages.next_page().await.unwrap().unwrap();
+        assert_eq!(first.cards.len(), 10);
+        assert!(!second.cards.is_empty());
+        assert_ne!(first.cards[0], second.cards[0]);
+    }
+}
@@ -0,0 +1,201 @@
+#![deny(missing_docs)]
+use std::future::Future;
+
+use mtg_api::MtgClient;
+use reqwest::Response;
+use serde::{Deserialize, Serialize};
+
+use crate::MTGCardError;
+
+/// A Magic The Gathering set, such as Khans of Tarkir
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+#[serde(default)]
+pub struct Set {
+    code: String,
+    name: String,
+    #[serde(rename = "type")]
+    set_type: String,
+    release_date: String,
+    block: String,
+    online_only: bool,
+}
+
+impl Set {
+    /// The short code of the set, e.g. `KTK`
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// The full name of the set, e.g. `Khans of Tarkir`
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The kind of set, e.g. `expansion` or `core`
+    pub fn set_type(&self) -> &str {
+        &self.set_type
+    }
+
+    /// When the set was released, formatted as `YYYY-MM-DD`
+    pub fn release_date(&self) -> &str {
+        &self.release_date
+    }
+
+    /// The block the set belongs to, empty if it is not part of one
+    pub fn block(&self) -> &str {
+        &self.block
+    }
+
+    /// Whether the set was only released digitally, such as on Magic Online
+    pub fn is_online_only(&self) -> bool {
+        self.online_only
+    }
+
+    /// Whether the cards of the set aren't played in tournaments, being from a funny or
+    /// digital only set
+    pub fn is_casual(&self) -> bool {
+        self.online_only || self.set_type == "funny"
+    }
+}
+
+/// Wrapper struct for set responses
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
+pub struct MultiSets {
+    /// The sets being wrapped
+    pub sets: Vec<Set>,
+}
+
+impl MultiSets {
+    /// Attempt to convert a [`Response`] into [`MultiSets`]
+    pub async fn from_response(res: Response) -> Result<Self, MTGCardError> {
+        Self::from_body(mtg_api::check_for_empty(res).await?)
+    }
+
+    fn from_body(body: Option<String>) -> Result<Self, MTGCardError> {
+        match body {
+            Some(json) => Ok(serde_json::from_str(&json)?),
+            None => Err(MTGCardError::NoCardError {}),
+        }
+    }
+}
+
+/// Find every set and return them deserialised into [`MultiSets`]
+///
+/// Once [`warm_sets_cache`] has run the sets are read from its cache without a request.
+pub async fn sets_find() -> Result<MultiSets, MTGCardError> {
+    MultiSets::from_body(mtg_api::sets_body().await?)
+}
+
+/// Like [`sets_find`] but sends the request through the given client
+///
+/// Once [`SetsCache::warm_sets_cache`] has run on the client, or any of its clones, the
+/// sets are read from its cache without a request.
+pub async fn sets_find_with(client: &MtgClient) -> Result<MultiSets, MTGCardError> {
+    MultiSets::from_body(client.sets_body().await?)
+}
+
+/// Fetch every set once for the functions without a client, such as [`crate::name_find_unique`]
+///
+/// See [`SetsCache::warm_sets_cache`].
+pub async fn warm_sets_cache() -> Result<(), MTGCardError> {
+    mtg_api::warm_sets().await?;
+    sets_find().await.map(|_| ())
+}
+
+/// Keeping the sets on an [`MtgClient`] for the lookups that need them
+pub trait SetsCache {
+    /// Fetch every set once and keep them on the client, shared with all of its clones
+    ///
+    /// Every lookup needing the sets through the client, such as [`sets_find_with`],
+    /// [`crate::name_find_unique_with`], [`crate::cards_in_sets_with`],
+    /// [`crate::cards_since_with`] and [`crate::format_pool_with`], then reads them from
+    /// the cache instead of requesting `/sets` again. The cache is empty until warmed and
+    /// warming again refreshes it. Errors if the sets can't be fetched or read.
+    fn warm_sets_cache(&self) -> impl Future<Output = Result<(), MTGCardError>> + Send;
+}
+
+impl SetsCache for MtgClient {
+    async fn warm_sets_cache(&self) -> Result<(), MTGCardError> {
+        self.warm_sets().await?;
+        sets_find_with(self).await.map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mtg_api::mock;
+
+    #[test]
+    fn parse_sets() {
+        let json = r#"{"sets":[
+            {"code":"KTK","name":"Khans of Tarkir","type":"expansion","releaseDate":"2014-09-26","block":"Khans of Tarkir"},
+            {"code":"M15","name":"Magic 2015","type":"core","releaseDate":"2014-07-18"},
+            {"code":"UST","name":"Unstable","type":"funny"},
+            {"code":"ME4","name":"Masters Edition IV","type":"masters","onlineOnly":true}
+        ]}"#;
+        let sets: MultiSets = serde_json::from_str(json).unwrap();
+        assert_eq!(sets.sets.len(), 4);
+        assert_eq!(sets.sets[0].set_type(), "expansion");
+        assert_eq!(sets.sets[0].block(), "Khans of Tarkir");
+        assert_eq!(sets.sets[1].block(), "");
+        let casual: Vec<&str> = sets
+            .sets
+            .iter()
+            .filter(|s| s.is_casual())
+            .map(|s| s.code())
+            .collect();
+        assert_eq!(casual, ["UST", "ME4"]);
+        assert!(sets.sets[3].is_online_only());
+    }
+
+    #[tokio::test]
+    async fn read_sets_from_cache() {
+        let sets = mock::json(
+            r#"{"sets":[{"code":"KTK","releaseDate":"2014-09-26"},{"code":"FRF","releaseDate":"2015-01-23"}]}"#,
+        );
+        let frf = mock::json_page(
+            r#"{"cards":[{"name":"Monastery Mentor","set":"FRF"}]}"#,
+            100,
+            1,
+        );
+        let bolt = mock::json_page(
+            r#"{"cards":[{"name":"Lightning Bolt","set":"KTK"}]}"#,
+            100,
+            1,
+        );
+        let pool = mock::json_page(
+            r#"{"cards":[{"name":"Lightning Bolt","set":"KTK","legalities":[{"format":"Modern","legality":"Legal"}]}]}"#,
+            100,
+            1,
+        );
+        let server = mock::serve(vec![sets, frf, bolt, pool]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        client.warm_sets_cache().await.unwrap();
+
+        // Clones share the cache, so none of these ask for the sets again
+        let clone = client.clone();
+        assert_eq!(sets_find_with(&clone).await.unwrap().sets.len(), 2);
+        let since = crate::cards_since_with(&clone, "2015-01-01").await.unwrap();
+        assert_eq!(since.cards[0].name, "Monastery Mentor");
+        let unique = crate::name_find_unique_with(&client, "Lightning Bolt")
+            .await
+            .unwrap();
+        assert_eq!(unique.cards.len(), 1);
+        let modern = crate::format_pool_with(&client, "Modern").await.unwrap();
+        assert_eq!(modern.len(), 1);
+
+        let sets_hits = server.paths().iter().filter(|p| *p == "/sets").count();
+        assert_eq!(sets_hits, 1);
+        assert_eq!(server.hits(), 4);
+    }
+
+    #[tokio::test]
+    async fn find_khans() {
+        let sets = sets_find().await.unwrap();
+        let khans = sets.sets.iter().find(|s| s.code() == "KTK").unwrap();
+        assert_eq!(khans.name(), "Khans of Tarkir");
+        assert_eq!(khans.release_date(), "2014-09-26");
+    }
+}
@@ -0,0 +1,219 @@
+#![deny(missing_docs)]
+use futures_util::stream::{self, Stream, StreamExt};
+use mtg_api::{CardQuery, MAX_PAGE_SIZE};
+
+use crate::{Card, MTGCardError, MultiCards};
+
+/// Progress through the pages of a search
+struct Crawl<'a> {
+    /// The search being paged through
+    query: CardQuery<'a>,
+    /// The next page to fetch
+    page: u32,
+    /// The final page, known once the first page has been read
+    last_page: Option<u32>,
+    /// Cards of the current page still to be yielded, with their position on it
+    cards: std::iter::Enumerate<std::vec::IntoIter<Card>>,
+    /// Global index of the first card on the current page
+    offset: usize,
+    /// Set once an error has been yielded
+    failed: bool,
+}
+
+impl Crawl<'_> {
+    fn finished(&self) -> bool {
+        self.failed || self.last_page.is_some_and(|last| self.page > last)
+    }
+}
+
+/// A page of a search as read from its headers
+struct Page {
+    cards: MultiCards,
+    page_size: usize,
+    last_page: u32,
+}
+
+/// Fetch a page of the search
+async fn fetch_page(query: &CardQuery<'_>, page: u32) -> Result<Page, MTGCardError> {
+    let res = query
+        .clone()
+        .page(page)
+        .page_size(MAX_PAGE_SIZE)
+        .send()
+        .await?;
+    let (cards, header) = MultiCards::from_response_with_header(res).await?;
+    let page_size = header.page_size().max(1);
+    Ok(Page {
+        cards,
+        page_size,
+        last_page: header.total_count().div_ceil(page_size) as u32,
+    })
+}
+
+/// Every card matching the query with its global index, fetched one page at a time
+fn crawl(query: CardQuery<'_>) -> impl Stream<Item = Result<(usize, Card), MTGCardError>> + '_ {
+    let start = Crawl {
+        query,
+        page: 1,
+        last_page: None,
+        cards: Vec::new().into_iter().enumerate(),
+        offset: 0,
+        failed: false,
+    };
+    stream::unfold(start, |mut crawl| async move {
+        loop {
+            if let Some((position, card)) = crawl.cards.next() {
+                return Some((Ok((crawl.offset + position, card)), crawl));
+            }
+            if crawl.finished() {
+                return None;
+            }
+            match fetch_page(&crawl.query, crawl.page).await {
+                Ok(page) => {
+                    crawl.offset = (crawl.page as usize - 1) * page.page_size;
+                    crawl.page += 1;
+                    crawl.last_page = Some(page.last_page);
+                    crawl.cards = page.cards.cards.into_iter().enumerate();
+                }
+                Err(MTGCardError::NoCardError {}) => return None,
+                Err(e) => {
+                    crawl.failed = true;
+                    return Some((Err(e), crawl));
+                }
+            }
+        }
+    })
+}
+
+/// Every card in the API, fetched one page at a time as the stream is polled
+///
+/// The number of pages is worked out from the `Total-Count` header of the first page, the
+/// stream also ends early if a page comes back empty. After an error the stream ends.
+pub fn all_cards() -> impl Stream<Item = Result<Card, MTGCardError>> {
+    CardQuery::new().stream()
+}
+
+/// Every card in the API paired with its index in the full list, starting from 0
+///
+/// For progress such as "card 247 of 93643", the total is
+/// [`total_count`](crate::total_count). Otherwise the same as [`all_cards`].
+pub fn all_cards_indexed() -> impl Stream<Item = Result<(usize, Card), MTGCardError>> {
+    CardQuery::new().stream_indexed()
+}
+
+/// Turns a [`CardQuery`] into a stream of every card it matches
+///
+/// ```no_run
+/// # async fn example() -> Result<(), mtg_cards::MTGCardError> {
+/// use futures_util::TryStreamExt;
+/// use mtg_cards::QueryStream;
+///
+/// let query = mtg_api::CardQuery::new().colors(&["red"]).types(&["creature"]);
+/// let cards: Vec<mtg_cards::Card> = query.stream().try_collect().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait QueryStream<'a> {
+    /// Every card matching the query, fetched one page at a time as the stream is polled
+    ///
+    /// Any page or page size set on the query is replaced. The stream ends after the last
+    /// page, as worked out from the `Total-Count` header, or after an error. Rate limited
+    /// pages are retried as configured on the client of the query, see
+    /// [`mtg_api::MtgClient::with_max_retries`].
+    fn stream(self) -> impl Stream<Item = Result<Card, MTGCardError>> + 'a;
+
+    /// Like [`QueryStream::stream`] with each card paired with its index in the full results
+    ///
+    /// The index is worked out from the page number and the position on the page, so it
+    /// stays correct on the last, partial page.
+    fn stream_indexed(self) -> impl Stream<Item = Result<(usize, Card), MTGCardError>> + 'a;
+}
+
+impl<'a> QueryStream<'a> for CardQuery<'a> {
+    fn stream(self) -> impl Stream<Item = Result<Card, MTGCardError>> + 'a {
+        crawl(self).map(|card| card.map(|(_, card)| card))
+    }
+
+    fn stream_indexed(self) -> impl Stream<Item = Result<(usize, Card), MTGCardError>> + 'a {
+        crawl(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mtg_api::{mock, MtgClient};
+
+    #[tokio::test]
+    async fn stream_query_pages() {
+        let server = mock::serve(vec![
+            mock::json_page(r#"{"cards":[{"name":"A"},{"name":"B"}]}"#, 2, 3),
+            mock::json_page(r#"{"cards":[{"name":"C"}]}"#, 2, 3),
+        ])
+        .await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let cards: Vec<Card> = client
+            .query()
+            .set("KTK")
+            .page(7)
+            .stream()
+            .map(|c| c.unwrap())
+            .collect()
+            .await;
+        let names: Vec<&str> = cards.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["A", "B", "C"]);
+
+        // The last page is known from the headers, so no empty page is requested
+        assert_eq!(
+            server.paths(),
+            vec![
+                "/cards?set=KTK&page=1&pageSize=100",
+                "/cards?set=KTK&page=2&pageSize=100"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_indexed_last_partial_page() {
+        let server = mock::serve(vec![
+            mock::json_page(r#"{"cards":[{"name":"A"},{"name":"B"}]}"#, 2, 5),
+            mock::json_page(r#"{"cards":[{"name":"C"},{"name":"D"}]}"#, 2, 5),
+            mock::json_page(r#"{"cards":[{"name":"E"}]}"#, 2, 5),
+        ])
+        .await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let cards: Vec<(usize, String)> = client
+            .query()
+            .stream_indexed()
+            .map(|c| c.map(|(i, card)| (i, card.name)).unwrap())
+            .collect()
+            .await;
+        let expected: Vec<(usize, String)> = ["A", "B", "C", "D", "E"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (i, name.to_string()))
+            .collect();
+        assert_eq!(cards, expected);
+    }
+
+    #[tokio::test]
+    async fn stream_ends_after_error() {
+        let failed = mock::respond("500 Internal Server Error", &[], "");
+        let server = mock::serve(vec![failed]).await;
+        let client = MtgClient::new().with_base_url(&server.url);
+        let results: Vec<_> = client.query().stream().collect().await;
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+
+    #[tokio::test]
+    async fn stream_two_pages() {
+        let cards: Vec<Card> = all_cards().take(150).map(|c| c.unwrap()).collect().await;
+        assert_eq!(cards.len(), 150);
+
+        // The first page holds 100 cards so the rest came from the second
+        let first_page = crate::page_find(1).await.unwrap();
+        assert_eq!(cards[..100], first_page.cards[..]);
+        assert_eq!(cards[0].name, "Ancestor's Chosen");
+    }
+}
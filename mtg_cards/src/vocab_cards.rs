@@ -0,0 +1,71 @@
+#![deny(missing_docs)]
+use reqwest::Response;
+use serde_json::Value;
+
+use crate::MTGCardError;
+
+/// Read the list of names held under `key`, e.g. `{"types":["Artifact", ...]}`
+async fn names_from_response(res: Response, key: &str) -> Result<Vec<String>, MTGCardError> {
+    match mtg_api::check_for_empty(res).await? {
+        Some(json) => parse(&json, key),
+        None => Err(MTGCardError::NoCardError {}),
+    }
+}
+
+fn parse(json: &str, key: &str) -> Result<Vec<String>, MTGCardError> {
+    let mut body: Value = serde_json::from_str(json)?;
+    Ok(serde_json::from_value(body[key].take())?)
+}
+
+/// Every card type, such as `Creature` or `Instant`
+pub async fn types_list() -> Result<Vec<String>, MTGCardError> {
+    names_from_response(mtg_api::types_all().await?, "types").await
+}
+
+/// Every subtype, such as `Human` or `Equipment`
+pub async fn subtypes_list() -> Result<Vec<String>, MTGCardError> {
+    names_from_response(mtg_api::subtypes_all().await?, "subtypes").await
+}
+
+/// Every supertype, such as `Legendary` or `Basic`
+pub async fn supertypes_list() -> Result<Vec<String>, MTGCardError> {
+    names_from_response(mtg_api::supertypes_all().await?, "supertypes").await
+}
+
+/// Every game format, such as `Standard` or `Commander`
+///
+/// Useful for checking a format name before searching by legality.
+pub async fn formats_list() -> Result<Vec<String>, MTGCardError> {
+    names_from_response(mtg_api::formats_all().await?, "formats").await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_names() {
+        let types = parse(r#"{"types":["Artifact","Creature"]}"#, "types").unwrap();
+        assert_eq!(types, ["Artifact", "Creature"]);
+
+        let wrong_key = parse(r#"{"types":["Artifact"]}"#, "subtypes");
+        assert!(matches!(wrong_key, Err(MTGCardError::WrappedSerde { .. })));
+    }
+
+    #[tokio::test]
+    async fn find_vocabulary() {
+        assert!(types_list().await.unwrap().contains(&"Creature".to_owned()));
+        assert!(subtypes_list().await.unwrap().contains(&"Human".to_owned()));
+        assert!(supertypes_list()
+            .await
+            .unwrap()
+            .contains(&"Legendary".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn find_formats() {
+        let formats = formats_list().await.unwrap();
+        assert!(formats.contains(&"Standard".to_owned()));
+        assert!(formats.contains(&"Commander".to_owned()));
+    }
+}